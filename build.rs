@@ -0,0 +1,50 @@
+//! Generates the day registry that is compiled into the `dispatcher` binary.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<(u8, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?;
+                    let is_day_file = stem.len() == 2
+                        && path.extension().is_some_and(|ext| ext == "rs")
+                        && stem.chars().all(|c| c.is_ascii_digit());
+                    let day: u8 = stem.parse().ok().filter(|_| is_day_file)?;
+                    (1..=25)
+                        .contains(&day)
+                        .then(|| (day, path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort_unstable();
+
+    // NOTE: day modules are skipped in test builds (they are tested as their own binaries) and
+    // when profiling heap usage, since every solution declares its own global allocator.
+    let cfg = "#[cfg(not(any(test, feature = \"dhat-heap\")))]";
+    let mut registry = String::new();
+
+    for (day, path) in &days {
+        registry.push_str(&format!(
+            "{cfg}\n#[allow(dead_code)]\n#[path = {path:?}]\nmod day_{day:02};\n\n"
+        ));
+    }
+
+    registry
+        .push_str("pub const SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (day, _) in &days {
+        registry.push_str(&format!("    {cfg}\n    day_{day:02}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
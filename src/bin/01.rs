@@ -1,43 +1,47 @@
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse = parse, impls = {
+    part_one: [plain = part_one_plain],
+    part_two: [plain = part_two_plain],
+});
 use polars::prelude::*;
-use std::io::{BufRead, Cursor};
-
-pub fn part_one(input: &str) -> Option<u64> {
-    // 1) Preprocess input: replace " " with "\t"
-    let processed_input: String = {
-        let mut lines_vec = vec![];
-        for line_result in Cursor::new(input).lines() {
-            let line = line_result.unwrap();
-            // Replace spaces with tabs
-            lines_vec.push(line.replace("   ", "\t"));
-        }
-        lines_vec.join("\n")
-    };
+use std::collections::HashMap;
+use std::num::ParseIntError;
+
+/// The location lists. The polars implementations turn them into a frame of their own.
+pub struct Lists {
+    left: Vec<u64>,
+    right: Vec<u64>,
+}
 
-    // 2) Determine how many columns we have at maximum
-    let max_cols = processed_input
-        .lines()
-        .map(|line| line.split('\t').count())
-        .max()
-        .unwrap_or(0);
-
-    // 3) Build a Polars schema from these columns
-    let mut schema = Schema::default();
-    for i in 0..max_cols {
-        schema.with_column(format!("column_{}", i).into(), DataType::Int64);
+pub fn parse(input: &str) -> Result<Lists, ParseIntError> {
+    let (mut left, mut right) = (vec![], vec![]);
+
+    for line in input.lines() {
+        let mut ids = line.split_whitespace();
+        if let (Some(l), Some(r)) = (ids.next(), ids.next()) {
+            left.push(l.parse()?);
+            right.push(r.parse()?);
+        }
     }
 
-    // 4) Build a new cursor from processed_input for Polars to read
-    let cursor = Cursor::new(processed_input);
+    Ok(Lists { left, right })
+}
 
-    // 5) Create the CSV reader with dynamic schema
-    let df = CsvReadOptions::default()
-        .with_has_header(false)
-        .with_schema(Some(Arc::new(schema)))
-        .map_parse_options(|parse_opts| parse_opts.with_separator(b'\t'))
-        .into_reader_with_file_handle(cursor)
-        .finish()
-        .unwrap();
+/// The lists as a frame with one `Int64` column per list.
+fn frame(lists: &Lists) -> PolarsResult<DataFrame> {
+    let column = |name: &str, ids: &[u64]| {
+        Series::new(name.into(), ids)
+            .cast(&DataType::Int64)
+            .map(Column::from)
+    };
+
+    DataFrame::new(vec![
+        column("column_0", &lists.left)?,
+        column("column_1", &lists.right)?,
+    ])
+}
+
+pub fn part_one(lists: &Lists) -> PolarsResult<u64> {
+    let df = frame(lists)?;
 
     println!("Original DataFrame:\n{}", df);
     // Define column names
@@ -51,21 +55,19 @@ pub fn part_one(input: &str) -> Option<u64> {
 
     for col_name in &col_names {
         if let Ok(column) = df.column(col_name) {
-            let sorted_col = column
-                .sort(SortOptions {
-                    descending: false,
-                    ..Default::default()
-                })
-                .unwrap();
+            let sorted_col = column.sort(SortOptions {
+                descending: false,
+                ..Default::default()
+            })?;
             sorted_columns.push(sorted_col);
         }
     }
-    let df = DataFrame::new(sorted_columns).unwrap();
+    let df = DataFrame::new(sorted_columns)?;
 
     println!("Sorted DataFrame:\n{}", df);
     // Compute the row-wise absolute differences
-    let col1 = df.column(col1_name).unwrap().i64().unwrap();
-    let col2 = df.column(col2_name).unwrap().i64().unwrap();
+    let col1 = df.column(col1_name)?.i64()?;
+    let col2 = df.column(col2_name)?.i64()?;
     let difference = (col1 - col2).apply(|v| v.map(|x| x.abs()));
 
     // Sum up the differences
@@ -75,45 +77,11 @@ pub fn part_one(input: &str) -> Option<u64> {
     println!("Sorted DataFrame:\n{}", df);
     println!("Sum of differences: {}", sum_difference);
 
-    Some(sum_difference as u64)
+    Ok(sum_difference as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    // 1) Preprocess input: replace " " with "\t"
-    let processed_input: String = {
-        let mut lines_vec = vec![];
-        for line_result in Cursor::new(input).lines() {
-            let line = line_result.unwrap();
-            // Replace spaces with tabs
-            lines_vec.push(line.replace("   ", "\t"));
-        }
-        lines_vec.join("\n")
-    };
-
-    // 2) Determine how many columns we have at maximum
-    let max_cols = processed_input
-        .lines()
-        .map(|line| line.split('\t').count())
-        .max()
-        .unwrap_or(0);
-
-    // 3) Build a Polars schema from these columns
-    let mut schema = Schema::default();
-    for i in 0..max_cols {
-        schema.with_column(format!("column_{}", i).into(), DataType::Int64);
-    }
-
-    // 4) Build a new cursor from processed_input for Polars to read
-    let cursor = Cursor::new(processed_input);
-
-    // 5) Create the CSV reader with dynamic schema
-    let df = CsvReadOptions::default()
-        .with_has_header(false)
-        .with_schema(Some(Arc::new(schema)))
-        .map_parse_options(|parse_opts| parse_opts.with_separator(b'\t'))
-        .into_reader_with_file_handle(cursor)
-        .finish()
-        .unwrap();
+pub fn part_two(lists: &Lists) -> PolarsResult<u64> {
+    let df = frame(lists)?;
 
     println!("Original DataFrame:\n{}", df);
 
@@ -122,8 +90,7 @@ pub fn part_two(input: &str) -> Option<u64> {
         .lazy()
         .group_by([col("column_1")])
         .agg([col("column_1").count().cast(DataType::Int64).alias("count")])
-        .collect()
-        .unwrap();
+        .collect()?;
 
     let joined = df
         .clone()
@@ -136,14 +103,13 @@ pub fn part_two(input: &str) -> Option<u64> {
         )
         // fill missing counts with 0
         .with_column(col("count").fill_null(lit(0)))
-        .collect()
-        .unwrap();
+        .collect()?;
 
     println!("Joined DF (with `count`):\n{joined}");
 
     // Compute the row-wise absolute differences
-    let col1 = joined.column("column_0").unwrap().i64().unwrap();
-    let col2 = joined.column("count").unwrap().i64().unwrap();
+    let col1 = joined.column("column_0")?.i64()?;
+    let col2 = joined.column("count")?.i64()?;
     let difference = (col1 * col2).apply(|v| v.map(|x| x.abs()));
 
     // Sum up the differences
@@ -153,7 +119,30 @@ pub fn part_two(input: &str) -> Option<u64> {
     println!("Sorted DataFrame:\n{}", df);
     println!("Sum of differences: {}", sum_difference);
 
-    Some(sum_difference as u64)
+    Ok(sum_difference as u64)
+}
+
+/// Plain Rust version of `part_one`, without polars.
+pub fn part_one_plain(lists: &Lists) -> Option<u64> {
+    let (mut left, mut right) = (lists.left.clone(), lists.right.clone());
+    left.sort_unstable();
+    right.sort_unstable();
+    Some(left.iter().zip(&right).map(|(l, r)| l.abs_diff(*r)).sum())
+}
+
+/// Plain Rust version of `part_two`, without polars.
+pub fn part_two_plain(lists: &Lists) -> Option<u64> {
+    let mut counts: HashMap<u64, u64> = HashMap::new();
+    for &id in &lists.right {
+        *counts.entry(id).or_default() += 1;
+    }
+    Some(
+        lists
+            .left
+            .iter()
+            .map(|id| id * counts.get(id).unwrap_or(&0))
+            .sum(),
+    )
 }
 
 #[cfg(test)]
@@ -162,13 +151,29 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result.unwrap(), 11);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result.unwrap(), 31);
+    }
+
+    #[test]
+    fn test_part_one_plain() {
+        let result =
+            part_one_plain(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result, Some(11));
+    }
+
+    #[test]
+    fn test_part_two_plain() {
+        let result =
+            part_two_plain(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(31));
     }
 }
@@ -1,43 +1,44 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse = parse, impls = {
+    part_one: [plain = part_one_plain],
+    part_two: [plain = part_two_plain],
+});
 use polars::prelude::*;
-use std::io::{BufRead, Cursor};
-
-pub fn part_one(input: &str) -> Option<u64> {
-    // 1) Preprocess input: replace " " with "\t"
-    let processed_input: String = {
-        let mut lines_vec = vec![];
-        for line_result in Cursor::new(input).lines() {
-            let line = line_result.unwrap();
-            // Replace spaces with tabs
-            lines_vec.push(line.replace(' ', "\t"));
-        }
-        lines_vec.join("\n")
-    };
+use std::num::ParseIntError;
+
+/// The levels of every report. The polars implementations turn them into a frame of their own.
+pub struct Reports {
+    levels: Vec<Vec<i64>>,
+}
 
-    // 2) Determine how many columns we have at maximum
-    let max_cols = processed_input
+pub fn parse(input: &str) -> Result<Reports, ParseIntError> {
+    let levels = input
         .lines()
-        .map(|line| line.split('\t').count())
-        .max()
-        .unwrap_or(0);
-
-    // 3) Build a Polars schema from these columns
-    let mut schema = Schema::default();
-    for i in 0..max_cols {
-        schema.with_column(format!("column_{}", i).into(), DataType::Int64);
-    }
+        .map(|line| line.split_whitespace().map(str::parse).collect())
+        .collect::<Result<_, _>>()?;
 
-    // 4) Build a new cursor from processed_input for Polars to read
-    let cursor = Cursor::new(processed_input);
+    Ok(Reports { levels })
+}
 
-    // 5) Create the CSV reader with dynamic schema
-    let mut df = CsvReadOptions::default()
-        .with_has_header(false)
-        .with_schema(Some(Arc::new(schema)))
-        .map_parse_options(|parse_opts| parse_opts.with_separator(b'\t'))
-        .into_reader_with_file_handle(cursor)
-        .finish()
-        .unwrap();
+/// The reports as a frame with one `Int64` column per level, null where a report is shorter.
+fn frame(reports: &Reports) -> PolarsResult<DataFrame> {
+    let max_cols = reports.levels.iter().map(Vec::len).max().unwrap_or(0);
+
+    let columns = (0..max_cols)
+        .map(|i| {
+            let values: Vec<Option<i64>> = reports
+                .levels
+                .iter()
+                .map(|levels| levels.get(i).copied())
+                .collect();
+            Column::new(format!("column_{i}").into(), values)
+        })
+        .collect();
+
+    DataFrame::new(columns)
+}
+
+pub fn part_one(reports: &Reports) -> PolarsResult<u64> {
+    let mut df = frame(reports)?;
 
     println!("Original DataFrame:\n{}", df);
 
@@ -97,7 +98,7 @@ pub fn part_one(input: &str) -> Option<u64> {
         .into();
 
     println!("Number of rows that meet all conditions = {value}");
-    Some(value)
+    Ok(value)
 }
 
 /// A helper that checks a row's sequence with *no* skips.
@@ -149,42 +150,8 @@ fn check_sequence_no_skip(row: &[Option<i64>]) -> (bool, bool, bool) {
 ///  2) if it fails, remove columns left-to-right until we find a pass or run out of columns
 ///
 /// We consider "valid" if (is_increasing || is_decreasing) && value_check.
-pub fn part_two(input: &str) -> Option<u64> {
-    // 1) Preprocess input: replace " " with "\t"
-    let processed_input: String = {
-        let mut lines_vec = vec![];
-        for line_result in Cursor::new(input).lines() {
-            let line = line_result.unwrap();
-            // Replace spaces with tabs
-            lines_vec.push(line.replace(' ', "\t"));
-        }
-        lines_vec.join("\n")
-    };
-
-    // 2) Determine how many columns we have at maximum
-    let max_cols = processed_input
-        .lines()
-        .map(|line| line.split('\t').count())
-        .max()
-        .unwrap_or(0);
-
-    // 3) Build a Polars schema from these columns
-    let mut schema = Schema::default();
-    for i in 0..max_cols {
-        schema.with_column(format!("column_{}", i).into(), DataType::Int64);
-    }
-
-    // 4) Build a new cursor from processed_input for Polars to read
-    let cursor = Cursor::new(processed_input);
-
-    // 5) Create the CSV reader with dynamic schema
-    let mut df = CsvReadOptions::default()
-        .with_has_header(false)
-        .with_schema(Some(Arc::new(schema)))
-        .map_parse_options(|parse_opts| parse_opts.with_separator(b'\t'))
-        .into_reader_with_file_handle(cursor)
-        .finish()
-        .unwrap();
+pub fn part_two(reports: &Reports) -> PolarsResult<u64> {
+    let mut df = frame(reports)?;
 
     println!("Original DataFrame:\n{}", df);
 
@@ -273,22 +240,149 @@ pub fn part_two(input: &str) -> Option<u64> {
         .into();
 
     println!("Number of rows that meet all conditions = {value}");
-    Some(value)
+    Ok(value)
+}
+
+fn is_safe(levels: &[i64]) -> bool {
+    let diffs: Vec<i64> = levels.windows(2).map(|w| w[1] - w[0]).collect();
+    diffs.iter().all(|d| (1..=3).contains(d)) || diffs.iter().all(|d| (-3..=-1).contains(d))
+}
+
+/// Plain Rust version of `part_one`, without polars.
+pub fn part_one_plain(reports: &Reports) -> Option<u64> {
+    Some(
+        reports
+            .levels
+            .iter()
+            .filter(|levels| is_safe(levels))
+            .count() as u64,
+    )
+}
+
+/// Plain Rust version of `part_two`, without polars: a report is also safe if removing any single level makes it safe.
+pub fn part_two_plain(reports: &Reports) -> Option<u64> {
+    Some(
+        reports
+            .levels
+            .iter()
+            .filter(|levels| {
+                is_safe(levels)
+                    || (0..levels.len()).any(|i| {
+                        let mut levels = levels.to_vec();
+                        levels.remove(i);
+                        is_safe(&levels)
+                    })
+            })
+            .count() as u64,
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::fuzz::{self, Rng};
+
+    /// Reports with mostly small steps, so that a good share of them is (almost) safe.
+    fn generate(rng: &mut Rng) -> String {
+        (0..rng.range(1..=8))
+            .map(|_| {
+                let is_increasing = rng.chance(0.5);
+                let mut level = rng.range(10..=50) as i64;
+                (0..rng.range(5..=8))
+                    .map(|_| {
+                        let step = rng.range(0..=4) as i64;
+                        level += if is_increasing == rng.chance(0.9) {
+                            step
+                        } else {
+                            -step
+                        };
+                        level.to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Reports read straight from the input, independent of `parse`.
+    fn reference_reports(input: &str) -> Vec<Vec<i64>> {
+        input
+            .lines()
+            .map(|line| line.split(' ').map(|x| x.parse().unwrap()).collect())
+            .collect()
+    }
+
+    /// The rules from the puzzle text, spelled out: all increasing or all decreasing, by 1 to 3.
+    fn is_safe_reference(levels: &[i64]) -> bool {
+        let increasing = levels.windows(2).all(|w| w[0] < w[1] && w[1] - w[0] <= 3);
+        let decreasing = levels.windows(2).all(|w| w[0] > w[1] && w[0] - w[1] <= 3);
+        increasing || decreasing
+    }
+
+    /// Slow reference for `part_one`, without the polars output of `part_one`.
+    fn part_one_reference(input: &str) -> Option<usize> {
+        let reports = reference_reports(input);
+        Some(reports.iter().filter(|r| is_safe_reference(r)).count())
+    }
+
+    /// Slow reference for `part_two`: try every report with each level left out.
+    fn part_two_reference(input: &str) -> Option<usize> {
+        let reports = reference_reports(input);
+        let is_tolerated = |levels: &Vec<i64>| {
+            is_safe_reference(levels)
+                || (0..levels.len()).any(|skip| {
+                    let rest: Vec<i64> = levels
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != skip)
+                        .map(|(_, level)| *level)
+                        .collect();
+                    is_safe_reference(&rest)
+                })
+        };
+        Some(reports.iter().filter(|r| is_tolerated(r)).count())
+    }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result.unwrap(), 2);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result.unwrap(), 4);
+    }
+
+    #[test]
+    fn test_part_one_plain() {
+        let result =
+            part_one_plain(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result, Some(2));
+    }
+
+    #[test]
+    fn test_part_two_plain() {
+        let result =
+            part_two_plain(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(4));
     }
+
+    #[test]
+    fn fuzz_part_one() {
+        fuzz::check(generate, part_one_reference, |input| {
+            part_one_plain(&parse(input).unwrap())
+        });
+    }
+
+    #[test]
+    fn fuzz_part_two() {
+        fuzz::check(generate, part_two_reference, |input| {
+            part_two_plain(&parse(input).unwrap())
+        });
+    }
 }
@@ -87,7 +87,7 @@ fn collect_do_dont(input: &str) -> Vec<String> {
 pub fn part_two(input: &str) -> Option<u64> {
     let chunks = collect_do_dont(input);
     let mut total = 0;
-    for (_i, chunk) in chunks.iter().enumerate() {
+    for chunk in chunks.iter() {
         total += part_one(chunk).unwrap();
     }
     Some(total)
@@ -136,20 +136,19 @@ pub fn part_two(input: &str) -> Option<u64> {
 
     // Collect lines and diagonals into a unified data vector
     let data: Vec<&str> = input.lines().collect();
-    let rows = data.len();
     let cols = data[0].len();
 
     // println!("Data: {:?}", data);
     let mut total = 0;
 
-    for r in 1..=(rows - 1) {
+    for (r, row) in data.iter().enumerate().skip(1) {
         // Start with Row 1 to find the 'A' and end 1 before
         for c in 1..=(cols - 1) {
             // Start with Col 1 to find the 'A' and end 1 before
             let mut sub_total = 0;
 
             // Step 1: Search for A in each row of Data
-            if data[r].chars().nth(c).unwrap() == 'A' {
+            if row.chars().nth(c).unwrap() == 'A' {
                 let check_data = extract_diagonals(input, r, c);
                 let diag_data: Vec<&str> = check_data.iter().map(|s| s.as_str()).collect();
                 for line in diag_data.iter() {
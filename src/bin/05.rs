@@ -1,15 +1,13 @@
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse);
 use std::collections::HashMap;
+use std::num::ParseIntError;
 
-fn build_lookup(record: Vec<String>) -> HashMap<String, usize> {
-    let mut row_map = HashMap::new();
-    for (col_index, value) in record.iter().enumerate() {
-        row_map.insert(value.clone(), col_index);
-    }
-    row_map
+pub struct Manual {
+    rules: HashMap<String, Vec<String>>,
+    data: Vec<Vec<String>>,
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn parse(input: &str) -> Result<Manual, String> {
     let mut rules: HashMap<String, Vec<String>> = HashMap::new();
     let mut data: Vec<Vec<String>> = Vec::new();
 
@@ -17,23 +15,38 @@ pub fn part_one(input: &str) -> Option<u64> {
     for line in input.lines() {
         if line.contains('|') {
             let parts: Vec<&str> = line.split('|').collect();
-            if parts.len() == 2 {
-                let key = parts[0].trim().to_string();
-                let value = parts[1].trim().to_string();
-
-                // Insert into the HashMap, appending to the vector if the key exists
-                rules.entry(key).or_insert_with(Vec::new).push(value);
+            if parts.len() != 2 {
+                return Err(format!("malformed rule `{line}`"));
             }
+            let key = parts[0].trim().to_string();
+            let value = parts[1].trim().to_string();
+
+            // Insert into the HashMap, appending to the vector if the key exists
+            rules.entry(key).or_default().push(value);
         } else if line.contains(',') {
             let entries: Vec<String> = line.split(',').map(|s| s.trim().to_string()).collect();
             data.push(entries);
         }
     }
 
+    Ok(Manual { rules, data })
+}
+
+fn build_lookup(record: Vec<String>) -> HashMap<String, usize> {
+    let mut row_map = HashMap::new();
+    for (col_index, value) in record.iter().enumerate() {
+        row_map.insert(value.clone(), col_index);
+    }
+    row_map
+}
+
+pub fn part_one(manual: &Manual) -> Result<u64, ParseIntError> {
+    let Manual { rules, data } = manual;
+
     let mut passed_records = Vec::new();
 
     // Step 2: Process each Data
-    for record in &data {
+    for record in data {
         // Step 2a: Build lookup table
         let lookup_table = build_lookup(record.clone());
         let mut rules_passed = true;
@@ -63,10 +76,10 @@ pub fn part_one(input: &str) -> Option<u64> {
     let mut total = 0;
     for record in passed_records {
         let mid_index = record.len() / 2;
-        total += record[mid_index].parse::<u64>().unwrap();
+        total += record[mid_index].parse::<u64>()?;
     }
 
-    Some(total)
+    Ok(total)
 }
 
 fn check_rules(
@@ -120,31 +133,13 @@ fn correctly_sort(input: Vec<String>, rules: HashMap<String, Vec<String>>) -> Ve
     }
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut rules: HashMap<String, Vec<String>> = HashMap::new();
-    let mut data: Vec<Vec<String>> = Vec::new();
-
-    // Step 1: Parse Input File for Rules and Data
-    for line in input.lines() {
-        if line.contains('|') {
-            let parts: Vec<&str> = line.split('|').collect();
-            if parts.len() == 2 {
-                let key = parts[0].trim().to_string();
-                let value = parts[1].trim().to_string();
-
-                // Insert into the HashMap, appending to the vector if the key exists
-                rules.entry(key).or_insert_with(Vec::new).push(value);
-            }
-        } else if line.contains(',') {
-            let entries: Vec<String> = line.split(',').map(|s| s.trim().to_string()).collect();
-            data.push(entries);
-        }
-    }
+pub fn part_two(manual: &Manual) -> Result<u64, ParseIntError> {
+    let Manual { rules, data } = manual;
 
     let mut passed_records = Vec::new();
 
     // Step 2: Process each Data
-    for record in &data {
+    for record in data {
         // Step 2a: Build lookup table
         let lookup_table = build_lookup(record.clone());
         // Step 3: Each Column in a row, check rules
@@ -165,10 +160,10 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut total = 0;
     for record in fixed_records {
         let mid_index = record.len() / 2;
-        total += record[mid_index].parse::<u64>().unwrap();
+        total += record[mid_index].parse::<u64>()?;
     }
 
-    Some(total)
+    Ok(total)
 }
 
 #[cfg(test)]
@@ -177,13 +172,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result, Ok(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result, Ok(123));
     }
 }
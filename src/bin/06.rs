@@ -250,14 +250,9 @@ struct Pos(i32, i32);
 pub fn part_one(input: &str) -> Option<u64> {
     let mut map = setup_map(input)?;
 
-    loop {
-        match next_step(&mut map) {
-            Some(move_spot) => {
-                map.current_location.push(move_spot.0);
-                map.current_direction = move_spot.1;
-            }
-            None => break,
-        }
+    while let Some(move_spot) = next_step(&mut map) {
+        map.current_location.push(move_spot.0);
+        map.current_direction = move_spot.1;
     }
     // Use HashSet to count unique locations
     let unique_locations: HashSet<_> = map.current_location.into_iter().collect();
@@ -62,16 +62,69 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::fuzz::{self, Rng};
+
+    const OPERATORS: [fn(u64, u64) -> u64; 3] = [
+        |a, b| a + b,
+        |a, b| a * b,
+        |a, b| a * 10u64.pow(b.to_string().len() as u32) + b,
+    ];
+
+    /// Tries every combination of the first `operators` operators, one after the other.
+    fn reference(input: &str, operators: usize) -> Option<u64> {
+        let mut total = 0;
+        for (answer, nums) in parse(input) {
+            let combinations = operators.pow(nums.len() as u32 - 1);
+            let is_solvable = (0..combinations).any(|mut combination| {
+                nums[1..].iter().fold(nums[0], |acc, &num| {
+                    let operator = OPERATORS[combination % operators];
+                    combination /= operators;
+                    operator(acc, num)
+                }) == answer
+            });
+            if is_solvable {
+                total += answer;
+            }
+        }
+        Some(total)
+    }
+
+    /// Equations that are solvable about half of the time.
+    fn generate(rng: &mut Rng) -> String {
+        (0..rng.range(1..=6))
+            .map(|_| {
+                let nums: Vec<u64> = (0..rng.range(1..=5)).map(|_| rng.range(1..=99)).collect();
+                let answer = if rng.chance(0.5) {
+                    nums[1..]
+                        .iter()
+                        .fold(nums[0], |acc, &num| rng.choose(&OPERATORS)(acc, num))
+                } else {
+                    rng.range(1..=10_000)
+                };
+                let nums: Vec<String> = nums.iter().map(ToString::to_string).collect();
+                format!("{answer}: {}", nums.join(" "))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
+        advent_of_code::template::examples::check(DAY, 1, part_one);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
+        advent_of_code::template::examples::check(DAY, 2, part_two);
+    }
+
+    #[test]
+    fn fuzz_part_one() {
+        fuzz::check(generate, |input| reference(input, 2), part_one);
+    }
+
+    #[test]
+    fn fuzz_part_two() {
+        fuzz::check(generate, |input| reference(input, 3), part_two);
     }
 }
@@ -4,15 +4,9 @@ use std::{
     ops::{Add, AddAssign, Sub},
 };
 
-#[derive(Clone, Copy, Default, Hash, Eq)]
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq)]
 struct Point(i32, i32);
 
-impl PartialEq for Point {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0 && self.1 == other.1
-    }
-}
-
 impl Add for Point {
     type Output = Self;
 
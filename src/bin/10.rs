@@ -1,10 +1,10 @@
 advent_of_code::solution!(10);
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(_input: &str) -> Option<u64> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(_input: &str) -> Option<u64> {
     None
 }
 
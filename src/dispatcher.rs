@@ -0,0 +1,39 @@
+//! Runs solutions in-process from the day registry generated by `build.rs`.
+//!
+//! `run_multi` builds this binary once and calls it for every day, instead of going through
+//! `cargo run --bin <day>` for each of them.
+use std::{env, process};
+
+use advent_of_code::template::{registry, Day, ANSI_BOLD, ANSI_RESET};
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+fn main() {
+    // NOTE: remaining arguments (e.g. `--time`) are read by the solutions themselves.
+    let days: Vec<Day> = env::args()
+        .skip(1)
+        .map_while(|arg| arg.parse().ok())
+        .collect();
+
+    if days.is_empty() {
+        for (i, solution) in SOLUTIONS.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            println!("{ANSI_BOLD}Day {}{ANSI_RESET}", solution.day);
+            println!("------");
+            (solution.run)();
+        }
+        return;
+    }
+
+    for day in days {
+        match registry::find(SOLUTIONS, day) {
+            Some(solution) => (solution.run)(),
+            None => {
+                eprintln!("Day {day} is not registered. Has it been scaffolded?");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, auth, download, examples, leaderboard, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,16 +9,38 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        bench_settings::BenchSettings,
+        commands::{auth::AuthCommand, solve::Implementations, time},
+        timeouts::Timeouts,
+        Day,
+    };
+    use std::{
+        num::{NonZeroU64, NonZeroUsize},
+        process,
+        time::Duration,
+    };
 
     pub enum AppArguments {
+        Auth {
+            command: AuthCommand,
+        },
         Download {
             day: Day,
+            force: bool,
         },
         Read {
             day: Day,
         },
+        Leaderboard {
+            id: u64,
+            day: Option<Day>,
+            source: Option<String>,
+        },
+        Examples {
+            day: Day,
+            overwrite: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
@@ -25,44 +49,169 @@ mod args {
         Solve {
             day: Day,
             release: bool,
+            time: bool,
             dhat: bool,
             submit: Option<u8>,
+            implementations: Implementations,
+            settings: BenchSettings,
         },
         All {
             release: bool,
+            jobs: usize,
+            settings: BenchSettings,
+            timeouts: Timeouts,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            check: Option<f64>,
+            memory: bool,
+            settings: BenchSettings,
+            timeouts: Timeouts,
+        },
+        TimeHistory {
+            day: Day,
+        },
+        Verify {
+            day: Option<Day>,
+            timeouts: Timeouts,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Bench settings from the environment, overridden by command-line flags.
+    fn parse_bench_settings(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchSettings, Box<dyn std::error::Error>> {
+        let mut settings = BenchSettings::from_env()?;
+
+        if let Some(budget) = args.opt_value_from_str("--budget")? {
+            settings.budget = Duration::from_millis(budget);
+        }
+        if let Some(min_samples) = args.opt_value_from_str("--min-samples")? {
+            settings.min_samples = min_samples;
+        }
+        if let Some(max_samples) = args.opt_value_from_str("--max-samples")? {
+            settings.max_samples = max_samples;
+        }
+        if let Some(warmup) = args.opt_value_from_str("--warmup")? {
+            settings.warmup = warmup;
+        }
+
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Implementations to run, from `--impl <name>` or `--compare`.
+    fn parse_implementations(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Implementations, Box<dyn std::error::Error>> {
+        let name: Option<String> = args.opt_value_from_str("--impl")?;
+
+        match (name, args.contains("--compare")) {
+            (Some(_), true) => Err("--impl and --compare can not be combined.".into()),
+            (Some(name), false) => Ok(Implementations::Named(name)),
+            (None, true) => Ok(Implementations::Compare),
+            (None, false) => Ok(Implementations::Default),
+        }
+    }
+
+    /// Time limits from the environment, with the global limit overridden by `--timeout <secs>`.
+    fn parse_timeouts(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Timeouts, Box<dyn std::error::Error>> {
+        let mut timeouts = Timeouts::from_env()?;
+
+        if let Some(secs) = args.opt_value_from_str::<_, NonZeroU64>("--timeout")? {
+            timeouts.default = Some(Duration::from_secs(secs.get()));
+        }
+
+        Ok(timeouts)
+    }
+
+    fn parse_auth(
+        args: &mut pico_args::Arguments,
+    ) -> Result<AuthCommand, Box<dyn std::error::Error>> {
+        match args.subcommand()?.as_deref() {
+            Some("add") => Ok(AuthCommand::Add {
+                name: args.free_from_str()?,
+                session: args.opt_free_from_str()?,
+            }),
+            Some("list") => Ok(AuthCommand::List),
+            Some("use") => Ok(AuthCommand::Use {
+                name: args.free_from_str()?,
+            }),
+            Some("check") => Ok(AuthCommand::Check {
+                name: args.opt_free_from_str()?,
+            }),
+            Some(x) => {
+                Err(format!("unknown auth command `{x}`, expected add, list, use or check.").into())
+            }
+            None => Err("expected an auth command: add, list, use or check.".into()),
+        }
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                jobs: args
+                    .opt_value_from_str::<_, NonZeroUsize>(["-j", "--jobs"])?
+                    .map_or(1, NonZeroUsize::get),
+                settings: parse_bench_settings(&mut args)?,
+                timeouts: parse_timeouts(&mut args)?,
+            },
+            Some("time") if args.contains("--history") => AppArguments::TimeHistory {
+                day: args.free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let check = args.contains("--check");
+                let memory = args.contains("--memory");
+                let threshold = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(time::DEFAULT_CHECK_THRESHOLD);
+                let settings = parse_bench_settings(&mut args)?;
+                let timeouts = parse_timeouts(&mut args)?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    check: check.then_some(threshold),
+                    memory,
+                    settings,
+                    timeouts,
                 }
             }
+            Some("verify") => AppArguments::Verify {
+                timeouts: parse_timeouts(&mut args)?,
+                day: args.opt_free_from_str()?,
+            },
+            Some("auth") => AppArguments::Auth {
+                command: parse_auth(&mut args)?,
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("leaderboard") => AppArguments::Leaderboard {
+                day: args.opt_value_from_str("--day")?,
+                source: args.opt_value_from_str("--source")?,
+                id: args.free_from_str()?,
+            },
+            Some("examples") => AppArguments::Examples {
+                day: args.free_from_str()?,
+                overwrite: args.contains("--overwrite"),
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
@@ -71,8 +220,11 @@ mod args {
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
+                time: args.contains("--time"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                implementations: parse_implementations(&mut args)?,
+                settings: parse_bench_settings(&mut args)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,10 +254,30 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All {
+                release,
+                jobs,
+                settings,
+                timeouts,
+            } => all::handle(release, jobs, &settings, &timeouts),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                check,
+                memory,
+                settings,
+                timeouts,
+            } => time::handle(day, all, store, check, memory, &settings, &timeouts),
+            AppArguments::TimeHistory { day } => time::handle_history(day),
+            AppArguments::Verify { day, timeouts } => verify::handle(day, &timeouts),
+            AppArguments::Auth { command } => auth::handle(command),
+            AppArguments::Download { day, force } => {
+                download::handle(day, force);
+            }
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Leaderboard { id, day, source } => leaderboard::handle(id, day, source),
+            AppArguments::Examples { day, overwrite } => examples::handle(day, overwrite),
             AppArguments::Scaffold {
                 day,
                 download,
@@ -113,22 +285,33 @@ fn main() {
             } => {
                 scaffold::handle(day, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(day, false);
                 }
             }
             AppArguments::Solve {
                 day,
                 release,
+                time,
+                dhat,
+                submit,
+                implementations,
+                settings,
+            } => solve::handle(
+                day,
+                release,
+                time,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                &implementations,
+                &settings,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
                         scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        // the description was just downloaded, so it is printed without fetching it again.
+                        read::print(&download::handle(day, false));
                     }
                     None => {
                         eprintln!(
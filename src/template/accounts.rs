@@ -0,0 +1,243 @@
+/// Advent of Code accounts of the current user, stored outside the repository, e.g. in `~/.config/advent-of-code/accounts.json`:
+///
+/// ```json
+/// { "active": "alice", "accounts": { "alice": "<session cookie>", "bob": "<session cookie>" } }
+/// ```
+///
+/// Inputs, answers and submissions of the active account are kept apart from those of other accounts.
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fs, io,
+    path::PathBuf,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::SESSION_ENV;
+
+/// Overrides where accounts are stored.
+pub const ACCOUNTS_FILE_ENV: &str = "AOC_ACCOUNTS_FILE";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Accounts {
+    pub active: Option<String>,
+    /// Session cookies by account name.
+    pub sessions: BTreeMap<String, String>,
+}
+
+impl Accounts {
+    /// Stores the session of an account. The first account becomes the active one.
+    pub fn add(&mut self, name: &str, session: &str) -> Result<(), String> {
+        check_name(name)?;
+
+        let session = session.trim();
+        if session.is_empty() {
+            return Err("session cookie must not be empty.".into());
+        }
+
+        self.sessions.insert(name.into(), session.into());
+        if self.active.is_none() {
+            self.active = Some(name.into());
+        }
+        Ok(())
+    }
+
+    pub fn switch(&mut self, name: &str) -> Result<(), String> {
+        if !self.sessions.contains_key(name) {
+            return Err(format!("no account named `{name}`."));
+        }
+        self.active = Some(name.into());
+        Ok(())
+    }
+
+    /// Name and session of the active account.
+    pub fn active_session(&self) -> Option<(&str, &str)> {
+        let name = self.active.as_deref()?;
+        Some((name, self.sessions.get(name)?))
+    }
+}
+
+/// Account names end up in paths like `data/inputs/<name>/01.txt`.
+fn check_name(name: &str) -> Result<(), String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "account names may only contain letters, digits, `-` and `_`, got `{name}`."
+        ))
+    }
+}
+
+pub fn get_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(ACCOUNTS_FILE_ENV) {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+
+    Some(config_dir.join("advent-of-code").join("accounts.json"))
+}
+
+/// Read the accounts of the current user. If none were stored yet, returns no accounts.
+pub fn read_from_file() -> Result<Accounts, String> {
+    let path = get_path().ok_or("could not determine the config directory.")?;
+
+    match fs::read_to_string(&path) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Accounts::default()),
+        Err(e) => Err(format!("could not read \"{}\": {e}", path.display())),
+    }
+}
+
+pub fn write_to_file(accounts: &Accounts) -> Result<PathBuf, String> {
+    let path = get_path().ok_or("could not determine the config directory.")?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+
+    let mut file = fs::OpenOptions::new();
+    file.write(true).create(true).truncate(true);
+
+    // session cookies give access to the account, so only the user may read them.
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut file, 0o600);
+
+    io::Write::write_all(
+        &mut file.open(&path).map_err(|e| e.to_string())?,
+        format(accounts).as_bytes(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(path)
+}
+
+/// The account whose session is used, if any.
+/// A session passed with `AOC_SESSION` does not belong to an account.
+pub fn active_name() -> Option<String> {
+    if env::var_os(SESSION_ENV).is_some() {
+        return None;
+    }
+    read_from_file().ok()?.active
+}
+
+/// The session of the active account.
+pub fn active_session() -> Option<String> {
+    let accounts = read_from_file().ok()?;
+    accounts
+        .active_session()
+        .map(|(_, session)| session.to_string())
+}
+
+fn parse(content: &str) -> Result<Accounts, String> {
+    let json = JsonValue::from_str(content).or(Err("accounts are not valid JSON."))?;
+
+    let json = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected accounts to be a JSON object.")?;
+
+    let active = match json.get("active") {
+        None | Some(JsonValue::Null) => None,
+        Some(JsonValue::String(name)) => Some(name.clone()),
+        Some(_) => return Err("Expected active to be null or a string.".into()),
+    };
+
+    let sessions = match json.get("accounts") {
+        None => BTreeMap::new(),
+        Some(accounts) => accounts
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected accounts to be a JSON object.")?
+            .iter()
+            .map(|(name, session)| match session {
+                JsonValue::String(session) => Ok((name.clone(), session.clone())),
+                _ => Err(format!("Expected session of `{name}` to be a string.")),
+            })
+            .collect::<Result<_, _>>()?,
+    };
+
+    // the file may have been edited by hand, so names are checked before they end up in paths.
+    for name in active.iter().chain(sessions.keys()) {
+        check_name(name)?;
+    }
+
+    Ok(Accounts { active, sessions })
+}
+
+fn format(accounts: &Accounts) -> String {
+    let sessions = accounts
+        .sessions
+        .iter()
+        .map(|(name, session)| (name.clone(), JsonValue::String(session.clone())))
+        .collect();
+
+    let json = JsonValue::Object(HashMap::from([
+        (
+            "active".to_string(),
+            accounts
+                .active
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        ),
+        ("accounts".to_string(), JsonValue::Object(sessions)),
+    ]));
+
+    format!("{}\n", json.format().unwrap_or_default())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn activates_first_account() {
+        let mut accounts = Accounts::default();
+        accounts.add("alice", "abc\n").unwrap();
+        accounts.add("bob", "def").unwrap();
+
+        assert_eq!(accounts.active_session(), Some(("alice", "abc")));
+
+        accounts.switch("bob").unwrap();
+        assert_eq!(accounts.active_session(), Some(("bob", "def")));
+    }
+
+    #[test]
+    fn rejects_invalid_accounts() {
+        let mut accounts = Accounts::default();
+        assert!(accounts.add("../alice", "abc").is_err());
+        assert!(accounts.add("", "abc").is_err());
+        assert!(accounts.add("alice", " ").is_err());
+        assert!(accounts.switch("alice").is_err());
+        assert_eq!(accounts, Accounts::default());
+    }
+
+    #[test]
+    fn roundtrips_accounts() {
+        let mut accounts = Accounts::default();
+        assert_eq!(parse(&format(&accounts)).unwrap(), accounts);
+
+        accounts.add("alice", "abc").unwrap();
+        accounts.add("bob-2", "def").unwrap();
+        assert_eq!(parse(&format(&accounts)).unwrap(), accounts);
+    }
+
+    #[test]
+    fn rejects_malformed_accounts() {
+        assert!(parse("[]").is_err());
+        assert!(parse(r#"{ "active": 1 }"#).is_err());
+        assert!(parse(r#"{ "accounts": { "alice": 1 } }"#).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_names_in_file() {
+        assert!(parse(r#"{ "active": "../x" }"#).is_err());
+        assert!(parse(r#"{ "accounts": { "a/b": "abc" } }"#).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+/// Store of accepted answers, one file per day and part, e.g. `data/answers/01-2.txt`.
+/// Answers of an account live in a directory of their own, e.g. `data/answers/alice/01-2.txt`.
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{accounts, Day};
+
+static ANSWERS_DIR: &str = "./data/answers";
+
+/// Outcome of comparing the answer of a part with the stored one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail,
+    /// No answer is stored for the part yet.
+    Unknown,
+}
+
+impl Check {
+    pub fn new(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (None, _) => Check::Unknown,
+            (Some(expected), Some(actual)) if expected.trim() == actual.trim() => Check::Pass,
+            (Some(_), _) => Check::Fail,
+        }
+    }
+}
+
+fn get_dir() -> PathBuf {
+    let dir = Path::new(ANSWERS_DIR);
+    match accounts::active_name() {
+        Some(name) => dir.join(name),
+        None => dir.to_path_buf(),
+    }
+}
+
+pub fn get_path(day: Day, part: u8) -> PathBuf {
+    path_in(&get_dir(), day, part)
+}
+
+fn path_in(dir: &Path, day: Day, part: u8) -> PathBuf {
+    dir.join(format!("{day}-{part}.txt"))
+}
+
+/// Reads the accepted answer of a part, if one was stored.
+pub fn read(day: Day, part: u8) -> Result<Option<String>, io::Error> {
+    match fs::read_to_string(get_path(day, part)) {
+        Ok(answer) => Ok(Some(answer.trim().to_string()).filter(|a| !a.is_empty())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Stores the accepted answer of a part.
+pub fn write(day: Day, part: u8, answer: &str) -> Result<(), io::Error> {
+    fs::create_dir_all(get_dir())?;
+    fs::write(get_path(day, part), format!("{}\n", answer.trim()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{path_in, Check, ANSWERS_DIR};
+    use crate::day;
+    use std::path::Path;
+
+    #[test]
+    fn checks_answers() {
+        assert_eq!(Check::new(Some("42"), Some("42")), Check::Pass);
+        assert_eq!(Check::new(Some("42\n"), Some("42")), Check::Pass);
+        assert_eq!(Check::new(Some("42"), Some("41")), Check::Fail);
+        assert_eq!(Check::new(Some("42"), None), Check::Fail);
+        assert_eq!(Check::new(None, Some("42")), Check::Unknown);
+        assert_eq!(Check::new(None, None), Check::Unknown);
+    }
+
+    #[test]
+    fn keys_answers_by_day_and_part() {
+        assert_eq!(
+            path_in(Path::new(ANSWERS_DIR), day!(1), 2),
+            Path::new("./data/answers").join("01-2.txt")
+        );
+    }
+}
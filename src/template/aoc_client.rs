@@ -0,0 +1,574 @@
+/// Native client for the Advent of Code website, authenticated with a session cookie.
+use std::{env, fmt::Display, fs, path::PathBuf, time::Duration};
+
+use crate::template::{accounts, inputs, markdown, puzzle, rate_limit::RateLimiter, Day};
+
+/// A session cookie, taking precedence over the active account and the session file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+/// Where to reach Advent of Code, e.g. a local stand-in server for testing.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+pub const YEAR_ENV: &str = "AOC_YEAR";
+/// Minimum time between two requests, shared by all processes.
+pub const REQUEST_INTERVAL_ENV: &str = "AOC_REQUEST_INTERVAL_SECS";
+/// How to reach whoever runs this repository, e.g. an email address or the URL of the repository.
+pub const USER_AGENT_CONTACT_ENV: &str = "AOC_USER_AGENT_CONTACT";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+/// Same session file as the one read by aoc-cli.
+const SESSION_FILE: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    BadYear(Option<String>),
+    BadRequestInterval(String),
+    /// The request did not reach the server or its response could not be read.
+    Transport(String),
+    Status(u16),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Add an account with \"cargo auth add <name>\", set {SESSION_ENV} or paste it into \"~/{SESSION_FILE}\"."
+            ),
+            ClientError::BadYear(None) => write!(
+                f,
+                "{YEAR_ENV} is not set. Set it in the [env] section of .cargo/config.toml."
+            ),
+            ClientError::BadYear(Some(year)) => {
+                write!(f, "{YEAR_ENV} must be a year, got `{year}`.")
+            }
+            ClientError::BadRequestInterval(interval) => write!(
+                f,
+                "{REQUEST_INTERVAL_ENV} must be a whole number of seconds, got `{interval}`."
+            ),
+            ClientError::Transport(e) => write!(f, "request to Advent of Code failed: {e}"),
+            ClientError::Status(404) => write!(
+                f,
+                "Advent of Code responded with 404, the puzzle might not be unlocked yet."
+            ),
+            ClientError::Status(400 | 500) => write!(
+                f,
+                "Advent of Code rejected the request, the session cookie might have expired."
+            ),
+            ClientError::Status(status) => {
+                write!(f, "Advent of Code responded with status {status}.")
+            }
+        }
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => ClientError::Status(status),
+            ureq::Error::Transport(e) => ClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(e: std::io::Error) -> Self {
+        ClientError::Transport(e.to_string())
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// Sends requests with `agent`, see `build_agent`.
+    pub fn new(base_url: &str, session: &str, year: u16, agent: ureq::Agent) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            year,
+            agent,
+        }
+    }
+
+    pub fn from_env() -> Result<Self, ClientError> {
+        Self::from_vars(
+            |key| env::var(key).ok(),
+            || accounts::active_session().or_else(read_session_file),
+        )
+    }
+
+    /// Same as `from_env`, but with the session of a specific account.
+    pub fn for_session(session: &str) -> Result<Self, ClientError> {
+        Self::from_vars(
+            |key| env::var(key).ok().filter(|_| key != SESSION_ENV),
+            || Some(session.to_string()),
+        )
+    }
+
+    fn from_vars(
+        get: impl Fn(&str) -> Option<String>,
+        stored_session: impl FnOnce() -> Option<String>,
+    ) -> Result<Self, ClientError> {
+        let session = get(SESSION_ENV)
+            .or_else(stored_session)
+            .filter(|session| !session.trim().is_empty())
+            .ok_or(ClientError::MissingSession)?;
+
+        let year = get(YEAR_ENV).ok_or(ClientError::BadYear(None))?;
+        let year = year
+            .trim()
+            .parse()
+            .map_err(|_| ClientError::BadYear(Some(year)))?;
+
+        let base_url = get(BASE_URL_ENV).unwrap_or_else(|| DEFAULT_BASE_URL.into());
+
+        let agent = build_agent(
+            &user_agent(get(USER_AGENT_CONTACT_ENV)),
+            RateLimiter::shared(request_interval(&get)?),
+        );
+
+        Ok(Self::new(&base_url, &session, year, agent))
+    }
+
+    /// The personal puzzle input of `day`.
+    pub fn fetch_input(&self, day: Day) -> Result<String, ClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// The HTML page of the puzzle of `day`, which includes part two once part one is solved.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, ClientError> {
+        self.get(&self.day_url(day))
+    }
+
+    /// Submits `answer` for one part of `day` and returns the text of the response, e.g. "That's the right answer! ...".
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+
+        Ok(article_text(&response.into_string()?))
+    }
+
+    /// The JSON API response of the private leaderboard `id`, which is only visible to its members.
+    pub fn fetch_leaderboard(&self, id: u64) -> Result<String, ClientError> {
+        self.get(&format!(
+            "{}/{}/leaderboard/private/view/{id}.json",
+            self.base_url, self.year
+        ))
+    }
+
+    /// The name of the logged in user, or `None` if the session is not valid (anymore).
+    pub fn fetch_user(&self) -> Result<Option<String>, ClientError> {
+        let page = self.get(&format!("{}/{}", self.base_url, self.year))?;
+
+        Ok(page.split_once("<div class=\"user\">").map(|(_, rest)| {
+            rest.split('<')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        }))
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        let response = self.agent.get(url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Every request of the agent waits for `rate_limiter` first.
+pub fn build_agent(user_agent: &str, rate_limiter: RateLimiter) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .user_agent(user_agent)
+        .middleware(rate_limiter)
+        .build()
+}
+
+/// Lets the maintainers of Advent of Code know who runs automated requests, as they ask to.
+/// Without a contact, falls back to the `repository` of Cargo.toml.
+fn user_agent(contact: Option<String>) -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    let contact = contact
+        .map(|contact| contact.trim().to_string())
+        .filter(|contact| !contact.is_empty())
+        .or_else(|| Some(env!("CARGO_PKG_REPOSITORY").to_string()).filter(|r| !r.is_empty()));
+
+    match contact {
+        Some(contact) => format!("advent_of_code/{version} (+{contact})"),
+        None => format!("advent_of_code/{version}"),
+    }
+}
+
+fn request_interval(get: impl Fn(&str) -> Option<String>) -> Result<Duration, ClientError> {
+    match get(REQUEST_INTERVAL_ENV) {
+        Some(interval) => Ok(Duration::from_secs(
+            interval
+                .trim()
+                .parse()
+                .map_err(|_| ClientError::BadRequestInterval(interval))?,
+        )),
+        None => Ok(DEFAULT_REQUEST_INTERVAL),
+    }
+}
+
+/// Fetches `url` without the session cookie, but with the same rate limit as requests to Advent of Code.
+pub fn fetch_url(url: &str) -> Result<String, ClientError> {
+    let interval = request_interval(|key| env::var(key).ok())?;
+    let agent = build_agent(
+        &user_agent(env::var(USER_AGENT_CONTACT_ENV).ok()),
+        RateLimiter::shared(interval),
+    );
+    let response = agent.get(url).call()?;
+    Ok(response.into_string()?)
+}
+
+fn read_session_file() -> Option<String> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    fs::read_to_string(PathBuf::from(home).join(SESSION_FILE)).ok()
+}
+
+#[must_use]
+pub fn get_puzzle_html_path(day: Day) -> String {
+    format!("data/puzzles/{day}.html")
+}
+
+/// Downloads the input and the puzzle description of `day` to the data directory. Returns the description.
+/// Inputs never change, so an input that was downloaded before is kept unless `force` is set.
+pub fn download(day: Day, force: bool) -> Result<String, ClientError> {
+    let client = Client::from_env()?;
+
+    let input_path = inputs::get_path(day);
+    if !force && inputs::read(&input_path).is_ok() {
+        println!("🎄 Input was already downloaded to \"{input_path}\", pass --force to download it again.");
+    } else {
+        fs::create_dir_all(inputs::get_dir())?;
+        fs::write(&input_path, client.fetch_input(day)?)?;
+        println!("🎄 Successfully wrote input to \"{input_path}\".");
+    }
+
+    download_puzzle(&client, day, force)
+}
+
+/// Downloads the puzzle page of `day` and writes its description as markdown, keeping the page next to it.
+/// A page that was downloaded before is only downloaded again if `force` is set or it does not contain part two
+/// yet, which may have been unlocked since, e.g. by solving part one on the website. Returns the markdown.
+pub fn download_puzzle(client: &Client, day: Day, force: bool) -> Result<String, ClientError> {
+    let html_path = get_puzzle_html_path(day);
+
+    let html = match fs::read_to_string(&html_path) {
+        Ok(html) if !force && markdown::count_parts(&html) >= 2 => html,
+        _ => {
+            let html = client.fetch_puzzle(day)?;
+            fs::write(&html_path, &html)?;
+            html
+        }
+    };
+
+    let markdown = markdown::from_puzzle_html(&html);
+    let puzzle_path = puzzle::get_path(day);
+    fs::write(&puzzle_path, &markdown)?;
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+
+    Ok(markdown)
+}
+
+/// The plain text of the `<article>` of a page, with tags removed and whitespace collapsed.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map_or(html, |(_, rest)| {
+            rest.split_once("</article>")
+                .map_or(rest, |(inner, _)| inner)
+        });
+
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    markdown::decode_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Instant,
+    };
+
+    use super::*;
+    use crate::{day, template::test_vars};
+
+    /// A request received by the stand-in server.
+    struct Request {
+        line: String,
+        cookie: Option<String>,
+        user_agent: Option<String>,
+        body: String,
+    }
+
+    /// Serves one canned response per request on a local port, and passes on every request it receives.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                let mut headers = HashMap::new();
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    match header.trim_end().split_once(": ") {
+                        Some((key, value)) => {
+                            headers.insert(key.to_lowercase(), value.to_string());
+                        }
+                        None => break,
+                    }
+                }
+
+                let length = headers
+                    .get("content-length")
+                    .map_or(0, |l| l.parse().unwrap());
+                let mut request_body = vec![0; length];
+                reader.read_exact(&mut request_body).unwrap();
+
+                tx.send(Request {
+                    line: line.trim_end().to_string(),
+                    cookie: headers.remove("cookie"),
+                    user_agent: headers.remove("user-agent"),
+                    body: String::from_utf8(request_body).unwrap(),
+                })
+                .unwrap();
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (base_url, rx)
+    }
+
+    const TEST_USER_AGENT: &str = "advent_of_code/test (+me@example.com)";
+
+    fn unlimited() -> ureq::Agent {
+        build_agent(TEST_USER_AGENT, RateLimiter::new(Duration::ZERO, None))
+    }
+
+    fn from_map(pairs: &[(&str, &str)], session_file: Option<&str>) -> Result<Client, ClientError> {
+        Client::from_vars(test_vars(pairs), || session_file.map(ToString::to_string))
+    }
+
+    #[test]
+    fn fetches_inputs_with_session() {
+        let (base_url, requests) = serve(vec![(200, "1 2 3\n")]);
+        let client = Client::new(&base_url, "abc\n", 2024, unlimited());
+
+        assert_eq!(client.fetch_input(day!(4)).unwrap(), "1 2 3\n");
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.line, "GET /2024/day/4/input HTTP/1.1");
+        assert_eq!(request.cookie.as_deref(), Some("session=abc"));
+        assert_eq!(request.user_agent.as_deref(), Some(TEST_USER_AGENT));
+    }
+
+    #[test]
+    fn fetches_puzzles() {
+        let (base_url, requests) = serve(vec![(200, "<main></main>")]);
+        let client = Client::new(&format!("{base_url}/"), "abc", 2023, unlimited());
+
+        assert_eq!(client.fetch_puzzle(day!(12)).unwrap(), "<main></main>");
+        assert_eq!(requests.recv().unwrap().line, "GET /2023/day/12 HTTP/1.1");
+    }
+
+    #[test]
+    fn limits_request_rate() {
+        let (base_url, _requests) = serve(vec![(200, "1"), (200, "2")]);
+        let client = Client::new(
+            &base_url,
+            "abc",
+            2024,
+            build_agent(
+                TEST_USER_AGENT,
+                RateLimiter::new(Duration::from_millis(200), None),
+            ),
+        );
+
+        let start = Instant::now();
+        client.fetch_input(day!(1)).unwrap();
+        client.fetch_input(day!(2)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, requests) = serve(vec![(
+            200,
+            "<main>\n<article><p>That&#39;s the right answer!  You are <em>one gold star</em> closer.</p></article>\n</main>",
+        )]);
+        let client = Client::new(&base_url, "abc", 2024, unlimited());
+
+        assert_eq!(
+            client.submit(day!(4), 2, "1234").unwrap(),
+            "That's the right answer! You are one gold star closer."
+        );
+
+        let request = requests.recv().unwrap();
+        assert_eq!(request.line, "POST /2024/day/4/answer HTTP/1.1");
+        assert_eq!(request.cookie.as_deref(), Some("session=abc"));
+        assert_eq!(request.body, "level=2&answer=1234");
+    }
+
+    #[test]
+    fn fetches_leaderboards() {
+        let (base_url, requests) = serve(vec![(200, "{}")]);
+        let client = Client::new(&base_url, "abc", 2024, unlimited());
+
+        assert_eq!(client.fetch_leaderboard(123_456).unwrap(), "{}");
+
+        let request = requests.recv().unwrap();
+        assert_eq!(
+            request.line,
+            "GET /2024/leaderboard/private/view/123456.json HTTP/1.1"
+        );
+        assert_eq!(request.cookie.as_deref(), Some("session=abc"));
+    }
+
+    #[test]
+    fn fetches_user_names() {
+        let (base_url, requests) = serve(vec![
+            (
+                200,
+                "<header><div class=\"user\">Alice Smith <span class=\"star-count\">12*</span></div></header>",
+            ),
+            (200, "<header><a href=\"/2024/auth/login\">[Log In]</a></header>"),
+        ]);
+        let client = Client::new(&base_url, "abc", 2024, unlimited());
+
+        assert_eq!(client.fetch_user().unwrap(), Some("Alice Smith".into()));
+        assert_eq!(client.fetch_user().unwrap(), None);
+        assert_eq!(requests.recv().unwrap().line, "GET /2024 HTTP/1.1");
+    }
+
+    #[test]
+    fn reports_error_statuses() {
+        let (base_url, _requests) = serve(vec![(404, "Not Found"), (400, "log in")]);
+        let client = Client::new(&base_url, "abc", 2024, unlimited());
+
+        assert!(matches!(
+            client.fetch_input(day!(25)),
+            Err(ClientError::Status(404))
+        ));
+        assert!(matches!(
+            client.fetch_input(day!(25)),
+            Err(ClientError::Status(400))
+        ));
+    }
+
+    #[test]
+    fn identifies_in_user_agent() {
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(
+            user_agent(Some(" me@example.com\n".into())),
+            format!("advent_of_code/{version} (+me@example.com)")
+        );
+        if env!("CARGO_PKG_REPOSITORY").is_empty() {
+            assert_eq!(user_agent(None), format!("advent_of_code/{version}"));
+        }
+    }
+
+    #[test]
+    fn reads_settings_from_env() {
+        let client = from_map(&[(YEAR_ENV, "2024")], Some("from-file\n")).unwrap();
+        assert_eq!(client.session, "from-file");
+        assert_eq!(client.base_url, DEFAULT_BASE_URL);
+
+        let client = from_map(
+            &[
+                (YEAR_ENV, "2024"),
+                (SESSION_ENV, "from-env"),
+                (BASE_URL_ENV, "http://localhost:8080/"),
+            ],
+            Some("from-file"),
+        )
+        .unwrap();
+        assert_eq!(client.session, "from-env");
+        assert_eq!(client.base_url, "http://localhost:8080");
+        assert_eq!(client.year, 2024);
+    }
+
+    #[test]
+    fn rejects_incomplete_settings() {
+        assert!(matches!(
+            from_map(&[(YEAR_ENV, "2024")], None),
+            Err(ClientError::MissingSession)
+        ));
+        assert!(matches!(
+            from_map(&[(YEAR_ENV, "2024")], Some(" \n")),
+            Err(ClientError::MissingSession)
+        ));
+        assert!(matches!(
+            from_map(&[(SESSION_ENV, "abc")], None),
+            Err(ClientError::BadYear(None))
+        ));
+        assert!(matches!(
+            from_map(&[(SESSION_ENV, "abc"), (YEAR_ENV, "last")], None),
+            Err(ClientError::BadYear(Some(_)))
+        ));
+        assert!(matches!(
+            from_map(
+                &[
+                    (SESSION_ENV, "abc"),
+                    (YEAR_ENV, "2024"),
+                    (REQUEST_INTERVAL_ENV, "soon")
+                ],
+                None
+            ),
+            Err(ClientError::BadRequestInterval(_))
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+/// Settings that control how the runner benches solutions.
+///
+/// Settings are read from environment variables, which `solve`, `time` and `all` also set for
+/// their child processes when the corresponding command-line flags are passed.
+use std::{collections::HashMap, env, fmt::Display, time::Duration};
+
+pub const BUDGET_ENV: &str = "AOC_BENCH_BUDGET_MS";
+pub const MIN_SAMPLES_ENV: &str = "AOC_BENCH_MIN_SAMPLES";
+pub const MAX_SAMPLES_ENV: &str = "AOC_BENCH_MAX_SAMPLES";
+pub const WARMUP_ENV: &str = "AOC_BENCH_WARMUP";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchSettings {
+    /// Approximate total execution time to spend on samples.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Untimed iterations to run before sampling.
+    pub warmup: u128,
+}
+
+impl Default for BenchSettings {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warmup: 0,
+        }
+    }
+}
+
+impl Display for BenchSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "budget {}ms, {}-{} samples, {} warmup",
+            self.budget.as_millis(),
+            self.min_samples,
+            self.max_samples,
+            self.warmup
+        )
+    }
+}
+
+impl BenchSettings {
+    /// Reads settings from the environment, falling back to defaults for unset variables.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    fn from_vars(get: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let parse = |key: &str| -> Result<Option<u128>, String> {
+            get(key)
+                .map(|value| {
+                    value.trim().parse().map_err(|_| {
+                        format!("{key} must be a non-negative integer, got `{value}`.")
+                    })
+                })
+                .transpose()
+        };
+
+        let defaults = Self::default();
+
+        let settings = Self {
+            budget: parse(BUDGET_ENV)?
+                .map(|ms| Duration::from_millis(u64::try_from(ms).unwrap_or(u64::MAX)))
+                .unwrap_or(defaults.budget),
+            min_samples: parse(MIN_SAMPLES_ENV)?.unwrap_or(defaults.min_samples),
+            max_samples: parse(MAX_SAMPLES_ENV)?.unwrap_or(defaults.max_samples),
+            warmup: parse(WARMUP_ENV)?.unwrap_or(defaults.warmup),
+        };
+
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_samples == 0 {
+            return Err("the minimum number of samples must be at least 1.".into());
+        }
+        if self.min_samples > self.max_samples {
+            return Err(format!(
+                "the minimum number of samples ({}) is larger than the maximum ({}).",
+                self.min_samples, self.max_samples
+            ));
+        }
+        Ok(())
+    }
+
+    /// Environment variables that pass these settings on to a child process.
+    pub fn to_env(&self) -> HashMap<&'static str, String> {
+        HashMap::from([
+            (BUDGET_ENV, self.budget.as_millis().to_string()),
+            (MIN_SAMPLES_ENV, self.min_samples.to_string()),
+            (MAX_SAMPLES_ENV, self.max_samples.to_string()),
+            (WARMUP_ENV, self.warmup.to_string()),
+        ])
+    }
+
+    /// Number of samples to take for a function that took `base_time` on its first execution.
+    pub fn iterations(&self, base_time: Duration) -> u128 {
+        (self.budget.as_nanos() / base_time.as_nanos().max(10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchSettings, BUDGET_ENV, MAX_SAMPLES_ENV, MIN_SAMPLES_ENV, WARMUP_ENV};
+    use crate::template::test_vars;
+    use std::time::Duration;
+
+    #[test]
+    fn uses_defaults() {
+        assert_eq!(
+            BenchSettings::from_vars(test_vars(&[])).unwrap(),
+            BenchSettings::default()
+        );
+    }
+
+    #[test]
+    fn reads_variables() {
+        let settings = BenchSettings::from_vars(test_vars(&[
+            (BUDGET_ENV, "250"),
+            (MIN_SAMPLES_ENV, "5"),
+            (MAX_SAMPLES_ENV, "50"),
+            (WARMUP_ENV, "3"),
+        ]))
+        .unwrap();
+        assert_eq!(settings.budget, Duration::from_millis(250));
+        assert_eq!(settings.min_samples, 5);
+        assert_eq!(settings.max_samples, 50);
+        assert_eq!(settings.warmup, 3);
+    }
+
+    #[test]
+    fn roundtrips_through_env() {
+        let settings = BenchSettings {
+            budget: Duration::from_millis(20),
+            min_samples: 1,
+            max_samples: 2,
+            warmup: 7,
+        };
+        let vars = settings.to_env();
+        assert_eq!(
+            BenchSettings::from_vars(|key| vars.get(key).cloned()).unwrap(),
+            settings
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_numbers() {
+        BenchSettings::from_vars(test_vars(&[(WARMUP_ENV, "-1")])).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_inverted_bounds() {
+        BenchSettings::from_vars(test_vars(&[
+            (MIN_SAMPLES_ENV, "100"),
+            (MAX_SAMPLES_ENV, "10"),
+        ]))
+        .unwrap();
+    }
+
+    #[test]
+    fn clamps_iterations() {
+        let settings = BenchSettings::default();
+        assert_eq!(settings.iterations(Duration::from_secs(2)), 10);
+        assert_eq!(settings.iterations(Duration::from_millis(10)), 100);
+        assert_eq!(settings.iterations(Duration::from_nanos(1)), 10000);
+    }
+}
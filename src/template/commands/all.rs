@@ -1,5 +1,15 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    all_days, bench_settings::BenchSettings, run_multi::run_multi, timeouts::Timeouts,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, jobs: usize, settings: &BenchSettings, timeouts: &Timeouts) {
+    run_multi(
+        &all_days().collect(),
+        is_release,
+        false,
+        false,
+        jobs,
+        settings,
+        timeouts,
+    );
 }
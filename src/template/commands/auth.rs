@@ -0,0 +1,128 @@
+use std::{env, process};
+
+use crate::template::{
+    accounts::{self, Accounts},
+    aoc_client::{Client, SESSION_ENV},
+    prompt,
+};
+
+pub enum AuthCommand {
+    /// Stores the session cookie of an account. Asks for it if not passed, to keep it out of the shell history.
+    Add {
+        name: String,
+        session: Option<String>,
+    },
+    List,
+    Use {
+        name: String,
+    },
+    /// Checks whether the session of one or all accounts is still logged in.
+    Check {
+        name: Option<String>,
+    },
+}
+
+pub fn handle(command: AuthCommand) {
+    let mut accounts = accounts::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read accounts: {e}");
+        process::exit(1);
+    });
+
+    match command {
+        AuthCommand::Add { name, session } => {
+            let session =
+                session.unwrap_or_else(|| prompt::hidden(&format!("Session cookie for {name}: ")));
+
+            if let Err(e) = accounts.add(&name, &session) {
+                eprintln!("Failed to add account: {e}");
+                process::exit(1);
+            }
+
+            let path = write(&accounts);
+            println!("Added account `{name}` to \"{}\".", path.display());
+            if accounts.active.as_deref() == Some(name.as_str()) {
+                println!("`{name}` is the active account.");
+            } else {
+                println!("Run \"cargo auth use {name}\" to switch to it.");
+            }
+        }
+        AuthCommand::List => list(&accounts),
+        AuthCommand::Use { name } => {
+            if let Err(e) = accounts.switch(&name) {
+                eprintln!("Failed to switch account: {e}");
+                process::exit(1);
+            }
+
+            write(&accounts);
+            println!("Switched to account `{name}`.");
+        }
+        AuthCommand::Check { name } => check(&accounts, name.as_deref()),
+    }
+
+    if env::var_os(SESSION_ENV).is_some() {
+        println!("note: {SESSION_ENV} is set and takes precedence over the active account.");
+    }
+}
+
+fn list(accounts: &Accounts) {
+    if accounts.sessions.is_empty() {
+        println!("No accounts yet. Add one with \"cargo auth add <name>\".");
+        return;
+    }
+
+    for name in accounts.sessions.keys() {
+        let marker = if accounts.active.as_ref() == Some(name) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{marker} {name}");
+    }
+}
+
+fn check(accounts: &Accounts, name: Option<&str>) {
+    let names: Vec<&String> = match name {
+        Some(name) => match accounts.sessions.get_key_value(name) {
+            Some((name, _)) => vec![name],
+            None => {
+                eprintln!("No account named `{name}`.");
+                process::exit(1);
+            }
+        },
+        None => accounts.sessions.keys().collect(),
+    };
+
+    if names.is_empty() {
+        println!("No accounts yet. Add one with \"cargo auth add <name>\".");
+        return;
+    }
+
+    let mut is_valid = true;
+
+    for name in names {
+        let user = Client::for_session(&accounts.sessions[name]).and_then(|c| c.fetch_user());
+
+        match user {
+            Ok(Some(user)) => println!("{name}: ✔ logged in as {user}"),
+            Ok(None) => {
+                is_valid = false;
+                println!("{name}: ✖ not logged in, the session cookie might have expired.");
+            }
+            Err(e) => {
+                is_valid = false;
+                println!("{name}: ✖ {e}");
+            }
+        }
+    }
+
+    if !is_valid {
+        process::exit(1);
+    }
+}
+
+fn write(accounts: &Accounts) -> std::path::PathBuf {
+    accounts::write_to_file(accounts).unwrap_or_else(|e| {
+        eprintln!("Failed to store accounts: {e}");
+        process::exit(1);
+    })
+}
@@ -1,14 +1,10 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, Day};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Downloads the input and the puzzle description of `day`. Returns the description.
+pub fn handle(day: Day, force: bool) -> String {
+    aoc_client::download(day, force).unwrap_or_else(|e| {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    })
 }
@@ -0,0 +1,182 @@
+use std::{fs, process};
+
+use crate::template::examples::{self, Example};
+use crate::template::prompt;
+use crate::template::puzzle::{self, Section};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Number of lines shown of each code block when choosing the example.
+const PREVIEW_LINES: usize = 5;
+
+/// Let the user pick the example of each part from the code blocks of the downloaded puzzle description,
+/// then write the example files and merge their expected answers into the manifest.
+pub fn handle(day: Day, overwrite: bool) {
+    let puzzle_path = puzzle::get_path(day);
+
+    let markdown = fs::read_to_string(&puzzle_path).unwrap_or_else(|e| {
+        eprintln!(
+            "Failed to read puzzle description \"{}\": {e}. Try running `cargo download {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    });
+
+    let sections = puzzle::parse(&markdown);
+    let blocks: Vec<&str> = sections
+        .iter()
+        .flat_map(|section| section.blocks.iter().map(String::as_str))
+        .collect();
+
+    if blocks.is_empty() {
+        eprintln!("No code blocks found in \"{}\".", puzzle_path.display());
+        process::exit(1);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        print_block(i + 1, block);
+    }
+
+    // indices of the chosen blocks, one example file each.
+    let mut chosen: Vec<usize> = vec![];
+    let mut examples: Vec<Example> = vec![];
+    let mut default_block = 1;
+
+    for (part, section) in (1..).zip(&sections) {
+        let Some(block) = prompt_block(part, blocks.len(), default_block) else {
+            continue;
+        };
+        default_block = block;
+
+        let index = chosen.iter().position(|b| *b == block).unwrap_or_else(|| {
+            chosen.push(block);
+            examples.push(Example::new(&get_file_name(day, chosen.len())));
+            chosen.len() - 1
+        });
+
+        let answer = prompt_answer(part, section);
+        match part {
+            1 => examples[index].part_1 = answer,
+            _ => examples[index].part_2 = answer,
+        }
+    }
+
+    if examples.is_empty() {
+        println!("No example chosen, nothing to write.");
+        return;
+    }
+
+    if !overwrite {
+        for example in &examples {
+            let path = examples::get_example_path(&example.file);
+            if fs::read_to_string(&path).is_ok_and(|content| !content.trim().is_empty()) {
+                eprintln!(
+                    "Example file \"data/examples/{}\" is not empty. Use --overwrite to replace it.",
+                    example.file
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    for (example, block) in examples.iter().zip(&chosen) {
+        let path = examples::get_example_path(&example.file);
+        match fs::write(&path, blocks[block - 1]) {
+            Ok(()) => println!("Wrote example file \"data/examples/{}\"", example.file),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    // entries of other example files, e.g. added by hand, are kept.
+    let manifest_path = examples::get_manifest_path(day);
+    let existing = if manifest_path.exists() {
+        examples::read_manifest(day).unwrap_or_else(|e| {
+            eprintln!("Failed to read example manifest: {e}");
+            process::exit(1);
+        })
+    } else {
+        vec![]
+    };
+
+    match examples::write_manifest(day, &examples::merge(&existing, &examples)) {
+        Ok(()) => println!("Wrote example manifest \"data/examples/{day}.json\""),
+        Err(e) => {
+            eprintln!("Failed to write example manifest: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Example files follow the naming of `read_file_part`, e.g. `01.txt`, `01-2.txt`.
+fn get_file_name(day: Day, n: usize) -> String {
+    if n == 1 {
+        format!("{day}.txt")
+    } else {
+        format!("{day}-{n}.txt")
+    }
+}
+
+fn print_block(n: usize, block: &str) {
+    let lines: Vec<&str> = block.lines().collect();
+
+    println!("{ANSI_BOLD}[{n}]{ANSI_RESET} {} line(s)", lines.len());
+    for line in lines.iter().take(PREVIEW_LINES) {
+        println!("    {line}");
+    }
+    if lines.len() > PREVIEW_LINES {
+        println!("    ...");
+    }
+}
+
+/// Asks for the code block of a part. Returns `None` if the part should be skipped.
+fn prompt_block(part: u8, count: usize, default: usize) -> Option<usize> {
+    loop {
+        let input = prompt::line(&format!(
+            "Example for part {part} [{default}] (1-{count}, or `-` to skip): "
+        ));
+
+        match input.as_str() {
+            "" => return Some(default),
+            "-" => return None,
+            _ => match input.parse::<usize>() {
+                Ok(n) if (1..=count).contains(&n) => return Some(n),
+                _ => println!("Please enter a number between 1 and {count}."),
+            },
+        }
+    }
+}
+
+/// Asks for the expected answer of a part, suggesting the candidates found in its description.
+fn prompt_answer(part: u8, section: &Section) -> Option<String> {
+    let candidates = &section.answers;
+
+    if candidates.is_empty() {
+        let input = prompt::line(&format!(
+            "Expected answer for part {part} (leave empty if unknown): "
+        ));
+        return Some(input).filter(|answer| !answer.is_empty());
+    }
+
+    let list: Vec<String> = (1..)
+        .zip(candidates)
+        .map(|(n, candidate)| format!("[{n}] {candidate}"))
+        .collect();
+    println!("Candidate answers for part {part}: {}", list.join(", "));
+
+    let default = candidates.len();
+    let input = prompt::line(&format!(
+        "Expected answer for part {part} [{default}] (number, `=<answer>` for another answer, or `-` if unknown): "
+    ));
+
+    match input.as_str() {
+        "" => Some(candidates[default - 1].clone()),
+        "-" => None,
+        _ => match (input.strip_prefix('='), input.parse::<usize>()) {
+            (Some(answer), _) => Some(answer.trim().to_string()).filter(|a| !a.is_empty()),
+            (None, Ok(n)) if (1..=candidates.len()).contains(&n) => Some(candidates[n - 1].clone()),
+            (None, _) => Some(input),
+        },
+    }
+}
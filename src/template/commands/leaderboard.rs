@@ -0,0 +1,36 @@
+use std::{env, process};
+
+use crate::template::{
+    leaderboard::{self, Source, SOURCE_ENV},
+    Day, ANSI_BOLD, ANSI_RESET,
+};
+
+/// Shows a private leaderboard, or who finished a single `day` first.
+/// `source` overrides the file or URL configured with `AOC_LEADERBOARD_SOURCE`.
+pub fn handle(id: u64, day: Option<Day>, source: Option<String>) {
+    let source = Source::new(source.or_else(|| env::var(SOURCE_ENV).ok()).as_deref(), id);
+
+    let leaderboard = leaderboard::load(&source, id).unwrap_or_else(|e| {
+        eprintln!("failed to load leaderboard {id}: {e}");
+        process::exit(1);
+    });
+
+    match day {
+        Some(day) => {
+            println!(
+                "{ANSI_BOLD}Leaderboard {id}, day {day} of {}{ANSI_RESET}",
+                leaderboard.event
+            );
+            println!("------");
+            println!("{}", leaderboard::format_day(&leaderboard, day));
+        }
+        None => {
+            println!(
+                "{ANSI_BOLD}Leaderboard {id}, {}{ANSI_RESET}",
+                leaderboard.event
+            );
+            println!("------");
+            println!("{}", leaderboard::format_overview(&leaderboard));
+        }
+    }
+}
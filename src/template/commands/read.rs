@@ -1,15 +1,21 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_client::{self, Client},
+    Day,
+};
 
+/// Prints the puzzle description, downloading it again until it contains part two.
 pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    match Client::from_env().and_then(|client| aoc_client::download_puzzle(&client, day, false)) {
+        Ok(markdown) => print(&markdown),
+        Err(e) => {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        }
     }
+}
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+pub fn print(markdown: &str) {
+    println!("---\n{markdown}");
 }
@@ -1,10 +1,11 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Write},
     process,
 };
 
-use crate::template::Day;
+use crate::template::examples::{self, Example};
+use crate::template::{inputs, Day};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -19,17 +20,10 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
-}
-
 pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
+    let input_path = inputs::get_path(day);
     let example_path = format!("data/examples/{day}.txt");
+    let manifest_path = format!("data/examples/{day}.json");
     let module_path = format!("src/bin/{day}.rs");
 
     let mut file = match safe_create_file(&module_path, overwrite) {
@@ -54,26 +48,52 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
+    // inputs never change once downloaded, so only `download --force` may replace them.
+    match fs::create_dir_all(inputs::get_dir()).and_then(|()| safe_create_file(&input_path, false))
+    {
         Ok(_) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Keeping existing input file \"{}\"", &input_path);
+        }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
+    // examples and their expected answers are written by hand, so they are kept as well.
+    match safe_create_file(&example_path, false) {
         Ok(_) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Keeping existing example file \"{}\"", &example_path);
+        }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
 
+    let manifest = examples::format_manifest(&[Example::new(&format!("{day}.txt"))]);
+
+    match safe_create_file(&manifest_path, false)
+        .and_then(|mut file| file.write_all(manifest.as_bytes()))
+    {
+        Ok(()) => {
+            println!("Created example manifest \"{}\"", &manifest_path);
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Keeping existing example manifest \"{}\"", &manifest_path);
+        }
+        Err(e) => {
+            eprintln!("Failed to create example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
@@ -1,8 +1,29 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{bench_settings::BenchSettings, inputs, Day};
+
+/// Which implementations of each part to run.
+pub enum Implementations {
+    Default,
+    /// A single implementation, passed with `--impl <name>`.
+    Named(String),
+    /// All implementations, checked against each other and benched side by side.
+    Compare,
+}
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    time: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    implementations: &Implementations,
+    settings: &BenchSettings,
+) {
+    if !inputs::ensure(day) {
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,6 +39,19 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
+    if time {
+        cmd_args.push("--time".to_string());
+    }
+
+    match implementations {
+        Implementations::Default => {}
+        Implementations::Named(name) => {
+            cmd_args.push("--impl".to_string());
+            cmd_args.push(name.clone());
+        }
+        Implementations::Compare => cmd_args.push("--compare".to_string()),
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -25,10 +59,15 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
+        .envs(settings.to_env())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // e.g. an unknown `--impl` or disagreeing implementations.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -1,16 +1,34 @@
 use std::collections::HashSet;
+use std::process;
 
+use crate::template::bench_settings::BenchSettings;
+use crate::template::history::{self, HistoryEntry};
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timeouts::Timeouts;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Relative slowdown that `--check` tolerates if no `--threshold` is passed, in percent.
+pub const DEFAULT_CHECK_THRESHOLD: f64 = 10.0;
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    check_threshold: Option<f64>,
+    memory: bool,
+    settings: &BenchSettings,
+    timeouts: &Timeouts,
+) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if check_threshold.is_some() {
+                // when checking for regressions, re-run every day that has stored timings.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,12 +39,31 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(&days_to_run, true, true, memory, 1, settings, timeouts).unwrap();
+
+    let has_regression = check_threshold.is_some_and(|threshold| {
+        // only days that were run can be compared, whether they finished or not.
+        let baseline = Timings {
+            data: stored_timings
+                .data
+                .iter()
+                .filter(|t| days_to_run.contains(&t.day))
+                .cloned()
+                .collect(),
+        };
+        check_regressions(&timings, &baseline, threshold)
+    });
 
-    if store {
+    if store && has_regression {
+        eprintln!("Not storing benchmarks of a run that failed the check.");
+    } else if store {
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if let Err(e) = history::append(&HistoryEntry::new(timings, true, settings.clone())) {
+            eprintln!("Failed to append run to benchmark history: {e}");
+        }
+
         println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
@@ -37,4 +74,125 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if has_regression {
+        process::exit(1);
+    }
+}
+
+/// Print per-part deltas against the stored timings. Returns `true` if any part regressed.
+/// Stored parts that are missing from the new run, e.g. because they timed out or panicked, count as regressions.
+fn check_regressions(timings: &Timings, stored_timings: &Timings, threshold: f64) -> bool {
+    let deltas = timings.compare(stored_timings);
+    let missing = timings.missing(stored_timings);
+
+    println!();
+    println!("{ANSI_BOLD}Check (threshold: +{threshold}%){ANSI_RESET}");
+    println!("------");
+
+    if deltas.is_empty() && missing.is_empty() {
+        println!("No stored timings to compare against.");
+        return false;
+    }
+
+    let mut regressions = missing.len();
+
+    for (day, phase) in &missing {
+        println!("Day {day} {phase}: no timing in this run ✖ regression");
+    }
+
+    for delta in &deltas {
+        let is_regression = delta.is_regression(threshold / 100.0);
+        if is_regression {
+            regressions += 1;
+        }
+
+        println!(
+            "Day {} {}: {:.1?} -> {:.1?} ({:+.1}%){}",
+            delta.day,
+            delta.phase,
+            nanos_to_duration(delta.old_nanos),
+            nanos_to_duration(delta.new_nanos),
+            delta.relative() * 100.0,
+            if is_regression { " ✖ regression" } else { "" }
+        );
+    }
+
+    if regressions > 0 {
+        eprintln!("\n{regressions} part(s) regressed by more than {threshold}%.");
+    } else {
+        println!("\nNo regressions.");
+    }
+
+    regressions > 0
+}
+
+/// Print how the timings of `day` changed across stored runs.
+pub fn handle_history(day: Day) {
+    let entries = match history::read_from_file() {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("{ANSI_BOLD}Day {day} history{ANSI_RESET}");
+    println!("------");
+
+    let runs: Vec<(&HistoryEntry, &Timing)> = entries
+        .iter()
+        .filter_map(|entry| Some((entry, entry.timing(day)?)))
+        .collect();
+
+    if runs.is_empty() {
+        println!("No stored runs. Use `cargo time {day} --store` to record one.");
+        return;
+    }
+
+    println!(
+        "{:<16}  {:<9}  {:<7}  {:<18}  {:<18}  {:<18}  Settings",
+        "Date", "Commit", "Profile", "Parse", "Part 1", "Part 2"
+    );
+
+    let mut previous: Option<&Timing> = None;
+
+    for (entry, timing) in runs {
+        let deltas = previous.map_or_else(Vec::new, |previous| {
+            Timings {
+                data: vec![timing.clone()],
+            }
+            .compare(&Timings {
+                data: vec![previous.clone()],
+            })
+        });
+
+        let column = |phase: &str, value: &Option<String>| {
+            let Some(value) = value else {
+                return "-".to_string();
+            };
+            match deltas.iter().find(|d| d.phase == phase) {
+                Some(delta) => format!("{value} ({:+.1}%)", delta.relative() * 100.0),
+                None => value.clone(),
+            }
+        };
+
+        println!(
+            "{:<16}  {:<9}  {:<7}  {:<18}  {:<18}  {:<18}  {}",
+            history::format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.profile,
+            column("Parse", &timing.parse),
+            column("Part 1", &timing.part_1),
+            column("Part 2", &timing.part_2),
+            entry.bench_settings
+        );
+
+        previous = Some(timing);
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_to_duration(nanos: f64) -> std::time::Duration {
+    std::time::Duration::from_nanos(nanos.round() as u64)
 }
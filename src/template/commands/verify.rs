@@ -0,0 +1,109 @@
+use std::io::{self, Write};
+use std::path::Path;
+use std::process;
+
+use crate::template::answers::{self, Check};
+use crate::template::bench_settings::BenchSettings;
+use crate::template::inputs;
+use crate::template::run_multi::{child_commands, get_path_for_bin};
+use crate::template::timeouts::Timeouts;
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Run every scaffolded day (or just `day`) against its real input and compare the answers with the stored ones.
+pub fn handle(day: Option<Day>, timeouts: &Timeouts) {
+    let days: Vec<Day> = day.map_or_else(
+        || {
+            all_days()
+                .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+                .collect()
+        },
+        |day| vec![day],
+    );
+
+    let dispatcher = child_commands::build_dispatcher(true);
+
+    let (mut passed, mut failed, mut unknown, mut skipped) = (0, 0, 0, 0);
+
+    for day in days {
+        if let Err(e) = inputs::read(inputs::get_path(day)) {
+            println!("Day {day}: skipped, input {e}.");
+            skipped += 2;
+            continue;
+        }
+
+        let run = match child_commands::run_solution_buffered(
+            day,
+            true,
+            &BenchSettings::default(),
+            dispatcher.as_deref(),
+            timeouts.for_day(day),
+        ) {
+            Ok(run) => run,
+            Err(e) => {
+                eprintln!("Day {day}: failed to run solution: {e:?}");
+                process::exit(1);
+            }
+        };
+
+        if let Some(timeout) = run.timed_out {
+            println!("Day {day}: timed out after {timeout:?}.");
+        } else if run.results.is_empty() {
+            // show why the solution did not report anything, e.g. a panic.
+            let _ = io::stderr().write_all(&run.stderr);
+        }
+
+        for part in [1, 2] {
+            let expected = match answers::read(day, part) {
+                Ok(expected) => expected,
+                Err(e) => {
+                    eprintln!("Day {day} Part {part}: failed to read stored answer: {e}");
+                    process::exit(1);
+                }
+            };
+
+            let result = run.results.iter().find(|r| r.part == part);
+            let actual = result.and_then(|r| r.answer.as_deref());
+
+            let check = Check::new(expected.as_deref(), actual);
+            let actual = match (actual, result.and_then(|r| r.error.as_deref())) {
+                (Some(actual), _) => actual.to_string(),
+                (None, Some(error)) => format!("error: {error}"),
+                (None, None) => "not solved".to_string(),
+            };
+
+            match check {
+                Check::Pass => {
+                    passed += 1;
+                    println!("Day {day} Part {part}: ✔ pass ({actual})");
+                }
+                Check::Fail => {
+                    failed += 1;
+                    println!(
+                        "Day {day} Part {part}: ✖ fail ({actual}, expected {})",
+                        expected.unwrap_or_default()
+                    );
+                }
+                Check::Unknown => {
+                    unknown += 1;
+                    println!("Day {day} Part {part}: ? unknown ({actual})");
+                }
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{ANSI_BOLD}{passed} passed, {failed} failed, {unknown} unknown, {skipped} skipped{ANSI_RESET}"
+    );
+
+    if unknown > 0 {
+        println!(
+            "Store accepted answers as `data/answers/<day>-<part>.txt` to verify unknown parts."
+        );
+    }
+
+    // parts that could not be run were not verified either.
+    if failed > 0 || skipped > 0 {
+        process::exit(1);
+    }
+}
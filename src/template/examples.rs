@@ -0,0 +1,365 @@
+/// Examples of a day with their expected answers, listed in a manifest like `data/examples/01.json`:
+///
+/// ```json
+/// [
+///   { "file": "01.txt", "part_1": "11", "part_2": "31" },
+///   { "file": "01-2.txt", "part_1": null, "part_2": "48" }
+/// ]
+/// ```
+///
+/// Files are relative to `data/examples`. A part without an expected answer is not checked.
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::runner::IntoAnswer;
+use crate::template::Day;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Example {
+    /// An example without expected answers.
+    pub fn new(file: &str) -> Self {
+        Self {
+            file: file.into(),
+            part_1: None,
+            part_2: None,
+        }
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+fn get_examples_dir() -> PathBuf {
+    env::current_dir().unwrap().join("data").join("examples")
+}
+
+pub fn get_example_path(file: &str) -> PathBuf {
+    get_examples_dir().join(file)
+}
+
+pub fn get_manifest_path(day: Day) -> PathBuf {
+    get_examples_dir().join(format!("{day}.json"))
+}
+
+pub fn read_manifest(day: Day) -> Result<Vec<Example>, String> {
+    let path = get_manifest_path(day);
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("could not read {}: {e}", path.display()))?;
+    parse_manifest(&content)
+}
+
+/// Entries of `existing` updated with `new`: an example of `new` replaces the entry of the same file,
+/// entries of other files are kept. New files are appended.
+pub fn merge(existing: &[Example], new: &[Example]) -> Vec<Example> {
+    let mut merged: Vec<Example> = existing
+        .iter()
+        .map(|example| {
+            new.iter()
+                .find(|n| n.file == example.file)
+                .unwrap_or(example)
+                .clone()
+        })
+        .collect();
+
+    for example in new {
+        if !merged.iter().any(|m| m.file == example.file) {
+            merged.push(example.clone());
+        }
+    }
+
+    merged
+}
+
+pub fn write_manifest(day: Day, examples: &[Example]) -> Result<(), io::Error> {
+    fs::write(get_manifest_path(day), format_manifest(examples))
+}
+
+/// Run `func` on every example of `day` that lists an expected answer for `part`.
+///
+/// # Panics
+/// Panics if the manifest can not be read, if no example lists an expected answer for `part`,
+/// or if any example yields an unexpected answer. The message names every failing example.
+pub fn check<R: IntoAnswer>(day: Day, part: u8, func: impl Fn(&str) -> R) {
+    let examples = read_manifest(day).unwrap_or_else(|e| panic!("{e}"));
+    let dir = get_examples_dir();
+
+    // a test that checks nothing would pass for any solution.
+    assert!(
+        examples
+            .iter()
+            .any(|example| example.expected(part).is_some()),
+        "day {day} part {part}: no example lists an expected answer, add one to {}",
+        get_manifest_path(day).display()
+    );
+
+    let failures = run_examples(&examples, part, &dir, func);
+
+    assert!(
+        failures.is_empty(),
+        "day {day} part {part} failed {} example(s):\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+/// Returns a description of every example that failed.
+fn run_examples<R: IntoAnswer>(
+    examples: &[Example],
+    part: u8,
+    dir: &Path,
+    func: impl Fn(&str) -> R,
+) -> Vec<String> {
+    examples
+        .iter()
+        .filter_map(|example| {
+            let expected = example.expected(part)?;
+
+            let input = match fs::read_to_string(dir.join(&example.file)) {
+                Ok(input) => input,
+                Err(e) => return Some(format!("  {}: could not read file: {e}", example.file)),
+            };
+
+            match func(&input).into_answer() {
+                Ok(Some(answer)) if answer.to_string().trim() == expected.trim() => None,
+                Ok(Some(answer)) => Some(format!(
+                    "  {}: expected {expected}, got {answer}",
+                    example.file
+                )),
+                Ok(None) => Some(format!(
+                    "  {}: expected {expected}, got no answer",
+                    example.file
+                )),
+                Err(e) => Some(format!(
+                    "  {}: expected {expected}, got error: {e}",
+                    example.file
+                )),
+            }
+        })
+        .collect()
+}
+
+pub fn parse_manifest(content: &str) -> Result<Vec<Example>, String> {
+    let json = JsonValue::from_str(content).or(Err("manifest is not valid JSON."))?;
+
+    json.get::<Vec<JsonValue>>()
+        .ok_or("Expected manifest to be a JSON array.")?
+        .iter()
+        .map(Example::try_from)
+        .collect()
+}
+
+/// Formats a manifest with one example per line, so that it stays easy to edit by hand.
+pub fn format_manifest(examples: &[Example]) -> String {
+    let string = |s: &str| {
+        JsonValue::String(s.into())
+            .stringify()
+            .unwrap_or_else(|_| "null".into())
+    };
+    let answer = |s: &Option<String>| s.as_deref().map_or_else(|| "null".into(), string);
+
+    let lines: Vec<String> = examples
+        .iter()
+        .map(|e| {
+            format!(
+                "  {{ \"file\": {}, \"part_1\": {}, \"part_2\": {} }}",
+                string(&e.file),
+                answer(&e.part_1),
+                answer(&e.part_2)
+            )
+        })
+        .collect();
+
+    format!("[\n{}\n]\n", lines.join(",\n"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?
+            .clone();
+
+        // answers may be written as strings or as plain numbers.
+        let answer = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+            Some(JsonValue::Number(n)) if n.fract() == 0.0 => Ok(Some(format!("{n:.0}"))),
+            Some(_) => Err(format!(
+                "Expected example.{key} to be null, a string or an integer."
+            )),
+        };
+
+        Ok(Example {
+            file,
+            part_1: answer("part_1")?,
+            part_2: answer("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_manifest, merge, parse_manifest, run_examples, Example};
+    use std::{env, fs, process};
+
+    #[test]
+    fn parses_manifests() {
+        let examples = parse_manifest(
+            r#"[
+                { "file": "03.txt", "part_1": "161", "part_2": null },
+                { "file": "03-2.txt", "part_2": 48 }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    file: "03.txt".into(),
+                    part_1: Some("161".into()),
+                    part_2: None,
+                },
+                Example {
+                    file: "03-2.txt".into(),
+                    part_1: None,
+                    part_2: Some("48".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_manifests() {
+        parse_manifest(r#"[{ "file": "03.txt", "part_1": true }]"#).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_manifests() {
+        let examples = vec![
+            Example {
+                file: "03.txt".into(),
+                part_1: Some("161".into()),
+                part_2: None,
+            },
+            Example::new("03-2.txt"),
+        ];
+        let content = format_manifest(&examples);
+        assert_eq!(
+            content,
+            "[\n  { \"file\": \"03.txt\", \"part_1\": \"161\", \"part_2\": null },\n  { \"file\": \"03-2.txt\", \"part_1\": null, \"part_2\": null }\n]\n"
+        );
+        assert_eq!(parse_manifest(&content).unwrap(), examples);
+    }
+
+    #[test]
+    fn merges_manifests() {
+        let existing = vec![
+            Example {
+                file: "01.txt".into(),
+                part_1: Some("11".into()),
+                part_2: None,
+            },
+            Example {
+                file: "01-2.txt".into(),
+                part_1: None,
+                part_2: Some("48".into()),
+            },
+        ];
+        let new = vec![
+            Example {
+                file: "01.txt".into(),
+                part_1: Some("12".into()),
+                part_2: None,
+            },
+            Example::new("01-3.txt"),
+        ];
+
+        assert_eq!(
+            merge(&existing, &new),
+            vec![new[0].clone(), existing[1].clone(), new[1].clone()]
+        );
+        assert_eq!(merge(&[], &new), new);
+    }
+
+    #[test]
+    fn names_failing_examples() {
+        let dir = env::temp_dir().join(format!("aoc-examples-test-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.txt"), "1 2 3").unwrap();
+        fs::write(dir.join("b.txt"), "4 5").unwrap();
+
+        let examples = vec![
+            Example {
+                file: "a.txt".into(),
+                part_1: Some("6".into()),
+                part_2: Some("3".into()),
+            },
+            Example {
+                file: "b.txt".into(),
+                part_1: Some("10".into()),
+                part_2: None,
+            },
+            Example {
+                file: "missing.txt".into(),
+                part_1: None,
+                part_2: Some("1".into()),
+            },
+        ];
+
+        let sum = |input: &str| {
+            Some(
+                input
+                    .split_whitespace()
+                    .map(|x| x.parse::<u32>().unwrap())
+                    .sum::<u32>(),
+            )
+        };
+        let count = |input: &str| Some(input.split_whitespace().count());
+
+        let parse_error = |input: &str| input.parse::<u32>();
+
+        let part_1 = run_examples(&examples, 1, &dir, sum);
+        let part_2 = run_examples(&examples, 2, &dir, count);
+
+        let errors = run_examples(&examples[..1], 1, &dir, parse_error);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(part_1, vec!["  b.txt: expected 10, got 9"]);
+        assert_eq!(
+            errors,
+            vec!["  a.txt: expected 6, got error: invalid digit found in string"]
+        );
+        assert_eq!(part_2.len(), 1);
+        assert!(part_2[0].starts_with("  missing.txt: could not read file"));
+    }
+}
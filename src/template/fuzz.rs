@@ -0,0 +1,409 @@
+/// Differential testing of a fast solution against a slow, obviously correct reference.
+///
+/// A day registers a generator for random inputs and compares two implementations in its tests:
+///
+/// ```ignore
+/// #[test]
+/// fn fuzz_part_one() {
+///     advent_of_code::template::fuzz::check(generate, part_one_reference, part_one);
+/// }
+/// ```
+///
+/// Parts may return `Option` or `Result`. Failing inputs are shrunk line by line, token by token and number by number, and printed as a
+/// ready-to-paste example file. The number of cases and the seed are read from `AOC_FUZZ_CASES`
+/// and `AOC_FUZZ_SEED`, so a failure can be reproduced with the seed it reports.
+use std::{env, ops::RangeInclusive};
+
+use crate::template::{panics, runner::IntoAnswer};
+
+pub const CASES_ENV: &str = "AOC_FUZZ_CASES";
+pub const SEED_ENV: &str = "AOC_FUZZ_SEED";
+
+/// Upper bound of shrink attempts, so that slow solutions still fail in reasonable time.
+const MAX_SHRINK_ATTEMPTS: usize = 2000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FuzzSettings {
+    /// Number of random inputs to compare on.
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for FuzzSettings {
+    fn default() -> Self {
+        Self {
+            cases: 100,
+            seed: 2024,
+        }
+    }
+}
+
+impl FuzzSettings {
+    /// Reads settings from the environment, falling back to the defaults.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    fn from_vars(get: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let defaults = Self::default();
+
+        let cases = match get(CASES_ENV) {
+            Some(value) => match value.trim().parse() {
+                Ok(cases) if cases > 0 => cases,
+                _ => {
+                    return Err(format!(
+                        "{CASES_ENV} must be a positive integer, got `{value}`."
+                    ))
+                }
+            },
+            None => defaults.cases,
+        };
+
+        let seed = match get(SEED_ENV) {
+            Some(value) => value
+                .trim()
+                .parse()
+                .map_err(|_| format!("{SEED_ENV} must be an unsigned integer, got `{value}`."))?,
+            None => defaults.seed,
+        };
+
+        Ok(Self { cases, seed })
+    }
+}
+
+/// Small seeded pseudo random number generator (SplitMix64) for input generators.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, e.g. `rng.range(1..=9)`.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "cannot pick from an empty range.");
+        match (end - start).checked_add(1) {
+            Some(len) => start + self.next_u64() % len,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Returns `true` with the given probability.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    /// A random element of `items`. Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        assert!(!items.is_empty(), "cannot choose from an empty slice.");
+        &items[self.range(0..=items.len() as u64 - 1) as usize]
+    }
+}
+
+/// Compare `candidate` with `reference` on random inputs, using [`FuzzSettings`] from the environment.
+///
+/// # Panics
+/// Panics with the shrunk input if the answers differ, or if `candidate` panics or fails on an
+/// input that `reference` can solve.
+pub fn check<R: IntoAnswer, C: IntoAnswer>(
+    generate: impl Fn(&mut Rng) -> String,
+    reference: impl Fn(&str) -> R,
+    candidate: impl Fn(&str) -> C,
+) {
+    let settings = FuzzSettings::from_env().unwrap_or_else(|e| panic!("{e}"));
+    check_with(&settings, generate, reference, candidate);
+}
+
+/// Same as [`check`], with explicit settings.
+pub fn check_with<R: IntoAnswer, C: IntoAnswer>(
+    settings: &FuzzSettings,
+    generate: impl Fn(&mut Rng) -> String,
+    reference: impl Fn(&str) -> R,
+    candidate: impl Fn(&str) -> C,
+) {
+    let mut rng = Rng::new(settings.seed);
+
+    for case in 1..=settings.cases {
+        let input = generate(&mut rng);
+
+        if let Err(e) = run(&reference, &input) {
+            panic!(
+                "fuzz case {case} (seed {}): reference failed on a generated input: {e}\n{input}",
+                settings.seed
+            );
+        }
+
+        if compare(&reference, &candidate, &input).is_none() {
+            continue;
+        }
+
+        let shrunk = shrink(&input, |input| {
+            compare(&reference, &candidate, input).is_some()
+        });
+        let mismatch = compare(&reference, &candidate, &shrunk).unwrap_or_default();
+
+        panic!(
+            "fuzz case {case} (seed {}) failed: {mismatch}\n\
+            Shrunk from {} to {} line(s), paste it into an example file:\n\
+            ---\n{shrunk}\n---",
+            settings.seed,
+            input.lines().count(),
+            shrunk.lines().count(),
+        );
+    }
+}
+
+/// Runs a solution, catching panics. Answers are compared by their text, so that the reference
+/// and the candidate may use different types.
+fn run<R: IntoAnswer>(func: impl Fn(&str) -> R, input: &str) -> Result<Option<String>, String> {
+    // panics are reported by the caller, so that shrinking does not print one message per attempt.
+    let answer =
+        panics::catch(|| func(input).into_answer()).map_err(|report| report.to_string())?;
+
+    answer
+        .map(|answer| answer.map(|answer| answer.to_string()))
+        .map_err(|e| format!("error: {e}"))
+}
+
+/// Describes how `candidate` disagrees with `reference`, if it does.
+/// Inputs that the reference fails on are considered invalid and never disagree.
+fn compare<R: IntoAnswer, C: IntoAnswer>(
+    reference: impl Fn(&str) -> R,
+    candidate: impl Fn(&str) -> C,
+    input: &str,
+) -> Option<String> {
+    let expected = run(reference, input).ok()?;
+    let expected_str = expected.as_deref().unwrap_or("no answer");
+
+    match run(candidate, input) {
+        Ok(actual) if actual == expected => None,
+        Ok(actual) => Some(format!(
+            "expected {expected_str}, got {}",
+            actual.as_deref().unwrap_or("no answer")
+        )),
+        Err(e) => Some(format!("expected {expected_str}, candidate {e}")),
+    }
+}
+
+/// Greedily applies the first simplification that still fails until none does.
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut current = input.to_string();
+    let mut attempts = 0;
+
+    'outer: loop {
+        for simpler in simplifications(&current) {
+            attempts += 1;
+            if attempts > MAX_SHRINK_ATTEMPTS {
+                break 'outer;
+            }
+            if fails(&simpler) {
+                current = simpler;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+
+    current
+}
+
+/// Simpler variants of `input`, coarsest first: without chunks of lines, without single tokens,
+/// and with smaller numbers.
+fn simplifications(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.lines().collect();
+    let mut variants = vec![];
+
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        for start in (0..lines.len()).step_by(chunk) {
+            let kept: Vec<&str> = lines[..start]
+                .iter()
+                .chain(lines[(start + chunk).min(lines.len())..].iter())
+                .copied()
+                .collect();
+            if !kept.is_empty() {
+                variants.push(kept.join("\n"));
+            }
+        }
+        chunk /= 2;
+    }
+
+    for (start, end) in spans(input, |c| !c.is_whitespace()) {
+        // remove the token together with the whitespace that separates it, but keep line breaks.
+        let before = input[..start].trim_end_matches([' ', '\t']);
+        let (start, end) = if before.is_empty() || before.ends_with('\n') {
+            let after = input[end..].trim_start_matches([' ', '\t']);
+            (start, input.len() - after.len())
+        } else {
+            (before.len(), end)
+        };
+        let variant = format!("{}{}", &input[..start], &input[end..]);
+        if variant.lines().count() == lines.len() && !variant.trim().is_empty() {
+            variants.push(variant);
+        }
+    }
+
+    for (start, end) in spans(input, |c| c.is_ascii_digit()) {
+        let Ok(number) = input[start..end].parse::<u128>() else {
+            continue;
+        };
+        for smaller in [0, number / 2, number.saturating_sub(1)] {
+            if smaller < number {
+                variants.push(format!("{}{smaller}{}", &input[..start], &input[end..]));
+            }
+        }
+    }
+
+    variants.dedup();
+    variants
+}
+
+/// Byte ranges of the maximal runs of characters matching `predicate`.
+fn spans(text: &str, predicate: impl Fn(char) -> bool) -> Vec<(usize, usize)> {
+    let mut spans = vec![];
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match (predicate(c), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                spans.push((s, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(s) = start {
+        spans.push((s, text.len()));
+    }
+
+    spans
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_with, shrink, simplifications, FuzzSettings, Rng, CASES_ENV, SEED_ENV};
+    use crate::template::test_vars;
+
+    fn generate(rng: &mut Rng) -> String {
+        (0..rng.range(1..=8))
+            .map(|_| {
+                (0..rng.range(1..=5))
+                    .map(|_| rng.range(0..=99).to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn max(input: &str) -> Option<u64> {
+        input
+            .split_whitespace()
+            .map(|x| x.parse::<u64>().unwrap())
+            .max()
+    }
+
+    /// Forgets about the last line.
+    fn buggy_max(input: &str) -> Option<u64> {
+        let lines: Vec<&str> = input.lines().collect();
+        lines[..lines.len() - 1]
+            .iter()
+            .flat_map(|line| line.split_whitespace())
+            .map(|x| x.parse::<u64>().unwrap())
+            .max()
+    }
+
+    #[test]
+    fn reads_settings_from_env() {
+        assert_eq!(
+            FuzzSettings::from_vars(test_vars(&[])).unwrap(),
+            FuzzSettings::default()
+        );
+        assert_eq!(
+            FuzzSettings::from_vars(test_vars(&[(CASES_ENV, "10"), (SEED_ENV, "7")])).unwrap(),
+            FuzzSettings { cases: 10, seed: 7 }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_zero_cases() {
+        FuzzSettings::from_vars(test_vars(&[(CASES_ENV, "0")])).unwrap();
+    }
+
+    #[test]
+    fn generates_reproducible_numbers() {
+        let (mut a, mut b) = (Rng::new(1), Rng::new(1));
+        for _ in 0..100 {
+            let x = a.range(3..=5);
+            assert_eq!(x, b.range(3..=5));
+            assert!((3..=5).contains(&x));
+        }
+        assert_eq!(Rng::new(1).range(0..=u64::MAX), Rng::new(1).next_u64());
+    }
+
+    #[test]
+    fn passes_equal_solutions() {
+        check_with(&FuzzSettings::default(), generate, max, |input| {
+            input
+                .lines()
+                .filter_map(|line| {
+                    line.split_whitespace()
+                        .map(|x| x.parse::<u64>().unwrap())
+                        .max()
+                })
+                .max()
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "paste it into an example file")]
+    fn reports_differing_solutions() {
+        check_with(&FuzzSettings::default(), generate, max, buggy_max);
+    }
+
+    #[test]
+    #[should_panic(expected = "candidate panicked at")]
+    fn reports_panicking_solutions() {
+        check_with(
+            &FuzzSettings::default(),
+            generate,
+            max,
+            |_| -> Option<u64> { panic!("not implemented") },
+        );
+    }
+
+    #[test]
+    fn shrinks_failing_inputs() {
+        let input = "5 17 3\n40 2\n8 9\n91 1";
+        let fails = |input: &str| max(input) != buggy_max(input);
+
+        assert!(fails(input));
+        assert_eq!(shrink(input, fails), "0");
+    }
+
+    #[test]
+    fn keeps_lines_when_removing_tokens() {
+        let variants = simplifications("1 2\n3");
+        assert!(variants.contains(&"2\n3".to_string()));
+        assert!(variants.contains(&"1\n3".to_string()));
+        assert!(variants.contains(&"1 2\n0".to_string()));
+        assert!(variants.iter().all(|v| !v.trim().is_empty()));
+    }
+}
@@ -0,0 +1,145 @@
+/// Heap allocation metrics read from DHAT reports.
+use std::{collections::HashMap, str::FromStr};
+
+use tinyjson::JsonValue;
+
+/// When set, profiled solutions write one DHAT report per phase into this directory.
+pub const DHAT_DIR_ENV: &str = "AOC_DHAT_DIR";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// Bytes live at the point of maximum heap usage.
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
+impl HeapStats {
+    /// Sums up the program points of a `dhat-heap.json` report.
+    pub fn from_dhat_json(content: &str) -> Result<Self, String> {
+        let json = JsonValue::from_str(content).or(Err("not a valid DHAT report."))?;
+
+        let program_points = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected DHAT report to be an object.")?
+            .get("pps")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected DHAT report to have a `pps` array.")?;
+
+        program_points.iter().try_fold(Self::default(), |acc, pp| {
+            let pp = pp
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected DHAT program point to be an object.")?;
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let number = |key: &str| {
+                pp.get(key)
+                    .and_then(|v| v.get::<f64>())
+                    .map(|x| *x as u64)
+                    .ok_or(format!(
+                        "expected DHAT program point to have number `{key}`."
+                    ))
+            };
+
+            Ok(Self {
+                total_bytes: acc.total_bytes + number("tb")?,
+                peak_bytes: acc.peak_bytes + number("gb")?,
+                allocations: acc.allocations + number("tbk")?,
+            })
+        })
+    }
+
+    /// Combines the stats of phases that run one after another.
+    pub fn combine(&self, other: &Self) -> Self {
+        Self {
+            total_bytes: self.total_bytes + other.total_bytes,
+            peak_bytes: self.peak_bytes.max(other.peak_bytes),
+            allocations: self.allocations + other.allocations,
+        }
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, HeapStats};
+
+    #[test]
+    fn parses_dhat_reports() {
+        let report = r#"{
+            "dhatFileVersion": 2,
+            "mode": "rust-heap",
+            "pps": [
+                { "tb": 1024, "tbk": 2, "tl": 10, "mb": 1024, "mbk": 2, "gb": 512, "gbk": 1, "eb": 0, "ebk": 0, "fs": [1] },
+                { "tb": 100, "tbk": 5, "tl": 10, "mb": 40, "mbk": 2, "gb": 20, "gbk": 1, "eb": 0, "ebk": 0, "fs": [2] }
+            ],
+            "ftbl": ["[root]", "a", "b"]
+        }"#;
+
+        let stats = HeapStats::from_dhat_json(report).unwrap();
+        assert_eq!(stats.total_bytes, 1124);
+        assert_eq!(stats.peak_bytes, 532);
+        assert_eq!(stats.allocations, 7);
+    }
+
+    #[test]
+    fn parses_empty_reports() {
+        let stats = HeapStats::from_dhat_json(r#"{ "pps": [] }"#).unwrap();
+        assert_eq!(stats, HeapStats::default());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        HeapStats::from_dhat_json(r#"{ "pps": [{ "tb": 1 }] }"#).unwrap();
+    }
+
+    #[test]
+    fn combines_phases() {
+        let a = HeapStats {
+            total_bytes: 10,
+            peak_bytes: 8,
+            allocations: 1,
+        };
+        let b = HeapStats {
+            total_bytes: 20,
+            peak_bytes: 4,
+            allocations: 3,
+        };
+        assert_eq!(
+            a.combine(&b),
+            HeapStats {
+                total_bytes: 30,
+                peak_bytes: 8,
+                allocations: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
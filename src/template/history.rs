@@ -0,0 +1,268 @@
+/// History of stored benchmark runs.
+///
+/// Every `time --store` appends one entry per run as a JSON line, so that performance of a day
+/// can be followed over time.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    bench_settings::BenchSettings,
+    timings::{Timing, Timings},
+    Day,
+};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// A single stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Abbreviated hash of the checked out git commit, if available.
+    pub commit: Option<String>,
+    pub profile: String,
+    pub bench_settings: BenchSettings,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for `timings` that were just benched.
+    pub fn new(timings: Timings, is_release: bool, bench_settings: BenchSettings) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            commit: current_commit(),
+            profile: if is_release { "release" } else { "debug" }.into(),
+            bench_settings,
+            timings,
+        }
+    }
+
+    /// Timing of `day` in this run, if it was benched.
+    pub fn timing(&self, day: Day) -> Option<&Timing> {
+        self.timings.data.iter().find(|t| t.day == day)
+    }
+}
+
+/// Append an entry to the history file.
+pub fn append(entry: &HistoryEntry) -> Result<(), io::Error> {
+    let line = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_FILE_PATH)?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all entries from the history file. If not present, returns an empty history.
+pub fn read_from_file() -> Result<Vec<HistoryEntry>, String> {
+    match fs::read_to_string(HISTORY_FILE_PATH) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse(content: &str) -> Result<Vec<HistoryEntry>, String> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("history contains invalid JSON."))?;
+            HistoryEntry::try_from(&json)
+        })
+        .collect()
+}
+
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// Formats a unix timestamp as a UTC date and time, e.g. `2024-12-01 05:00`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let seconds = timestamp % 86400;
+
+    // civil date from days since epoch, see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "bench_settings".into(),
+            JsonValue::from(&value.bench_settings),
+        );
+        map.insert("timings".into(), JsonValue::from(value.timings.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected history.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected history.commit to be null or string.")?
+            .cloned();
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected history.profile to be a string.")?
+            .clone();
+
+        let bench_settings = json
+            .get("bench_settings")
+            .ok_or("Expected history to have key `bench_settings`.")
+            .map(BenchSettings::try_from)??;
+
+        let timings = json
+            .get("timings")
+            .ok_or("Expected history to have key `timings`.")
+            .map(Timings::try_from)??;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit,
+            profile,
+            bench_settings,
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, parse, HistoryEntry};
+    use crate::{
+        day,
+        template::{
+            bench_settings::BenchSettings,
+            timings::{Timing, Timings},
+        },
+    };
+    use tinyjson::JsonValue;
+
+    fn get_mock_entry() -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 1_733_029_200,
+            commit: Some("abc1234".into()),
+            profile: "release".into(),
+            bench_settings: BenchSettings::default(),
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(7),
+                    parse: None,
+                    part_1: Some("2.4µs".into()),
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
+                    total_nanos: 2400.0,
+                }],
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = get_mock_entry();
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let content = format!("{line}\n\n{line}\n");
+
+        let entries = parse(&content).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].timestamp, entry.timestamp);
+        assert_eq!(entries[0].commit, Some("abc1234".into()));
+        assert_eq!(entries[0].profile, "release");
+        assert_eq!(entries[0].bench_settings, BenchSettings::default());
+        assert_eq!(
+            entries[1].timing(day!(7)).unwrap().part_1,
+            Some("2.4µs".into())
+        );
+        assert_eq!(entries[1].timing(day!(8)).is_none(), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_entries() {
+        parse(r#"{ "timestamp": 0 }"#).unwrap();
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_029_200), "2024-12-01 05:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+}
@@ -0,0 +1,136 @@
+/// Checks that the input of a day is in place before its solution runs.
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use crate::template::{accounts, aoc_client, prompt, Day};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputError {
+    Missing,
+    /// The file exists, but holds nothing but whitespace, e.g. right after `cargo scaffold`.
+    Empty,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing => write!(f, "is missing"),
+            InputError::Empty => write!(f, "is empty"),
+        }
+    }
+}
+
+/// Inputs differ per account, so each account has its own directory, e.g. `data/inputs/alice`.
+#[must_use]
+pub fn get_dir() -> String {
+    match accounts::active_name() {
+        Some(name) => format!("data/inputs/{name}"),
+        None => "data/inputs".into(),
+    }
+}
+
+#[must_use]
+pub fn get_path(day: Day) -> String {
+    format!("{}/{day}.txt", get_dir())
+}
+
+/// Reads the input at `path`, failing if there is none.
+pub fn read(path: impl AsRef<Path>) -> Result<String, InputError> {
+    let input = fs::read_to_string(path).map_err(|_| InputError::Missing)?;
+
+    if input.trim().is_empty() {
+        Err(InputError::Empty)
+    } else {
+        Ok(input)
+    }
+}
+
+/// Explains what is wrong with the input of `day` and how to fix it.
+#[must_use]
+pub fn describe(day: Day, path: &str, error: &InputError) -> String {
+    format!(
+        "Input for day {day} {error} (\"{path}\"). Run \"cargo download {day}\" to download it."
+    )
+}
+
+/// Reads the input of `day` for its solution.
+/// Prints a hint instead, so that the solution is not run on no input and other days in the same process still run.
+#[must_use]
+pub fn read_or_report(day: Day) -> Option<String> {
+    let path = get_path(day);
+    read(&path)
+        .map_err(|e| eprintln!("{}", describe(day, &path, &e)))
+        .ok()
+}
+
+/// Checks that `day` has an input before it runs.
+/// If not, explains how to get it and, when run from a terminal, offers to download it right away.
+/// Returns whether the input is in place now.
+pub fn ensure(day: Day) -> bool {
+    let path = get_path(day);
+    let Err(e) = read(&path) else {
+        return true;
+    };
+
+    eprintln!("{}", describe(day, &path, &e));
+
+    if !io::stdin().is_terminal()
+        || !io::stdout().is_terminal()
+        || !prompt::confirm("Download it now? [y/N] ")
+    {
+        return false;
+    }
+
+    if let Err(e) = aoc_client::download(day, false) {
+        eprintln!("failed to download day {day}: {e}");
+        return false;
+    }
+
+    match read(&path) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("{}", describe(day, &path, &e));
+            false
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+
+    #[test]
+    fn reads_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-inputs-test-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("present.txt"), "1 2 3\n").unwrap();
+        fs::write(dir.join("empty.txt"), "").unwrap();
+        fs::write(dir.join("blank.txt"), "\n  \n").unwrap();
+
+        assert_eq!(read(dir.join("present.txt")), Ok("1 2 3\n".to_string()));
+        assert_eq!(read(dir.join("empty.txt")), Err(InputError::Empty));
+        assert_eq!(read(dir.join("blank.txt")), Err(InputError::Empty));
+        assert_eq!(read(dir.join("missing.txt")), Err(InputError::Missing));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn describes_problems() {
+        let day = Day::new(4).unwrap();
+        assert_eq!(
+            describe(day, "data/inputs/04.txt", &InputError::Empty),
+            "Input for day 04 is empty (\"data/inputs/04.txt\"). Run \"cargo download 04\" to download it."
+        );
+        assert_eq!(
+            describe(day, "data/inputs/04.txt", &InputError::Missing),
+            "Input for day 04 is missing (\"data/inputs/04.txt\"). Run \"cargo download 04\" to download it."
+        );
+    }
+}
@@ -0,0 +1,440 @@
+/// Private leaderboards, read from the JSON API of Advent of Code or from a file or URL configured with
+/// `AOC_LEADERBOARD_SOURCE`, e.g. a copy of the response shared with the team.
+///
+/// Advent of Code asks to fetch a leaderboard at most once every 15 minutes, so its responses are
+/// cached in `data/leaderboards/<id>.json` for that long.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client, history::format_timestamp, Day};
+
+/// Where to read leaderboards from instead, a path or an `http(s)://` URL. `{id}` is replaced with the leaderboard id.
+pub const SOURCE_ENV: &str = "AOC_LEADERBOARD_SOURCE";
+
+const CACHE_DIR: &str = "data/leaderboards";
+const CACHE_DURATION: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Url(String),
+    AdventOfCode,
+}
+
+impl Source {
+    /// The source of leaderboard `id`, given the configured source, if any.
+    pub fn new(source: Option<&str>, id: u64) -> Self {
+        let Some(source) = source.filter(|s| !s.trim().is_empty()) else {
+            return Source::AdventOfCode;
+        };
+
+        let source = source.trim().replace("{id}", &id.to_string());
+        if source.starts_with("http://") || source.starts_with("https://") {
+            Source::Url(source)
+        } else {
+            Source::File(PathBuf::from(source))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub event: String,
+    /// When the puzzle of day 1 unlocked, if part of the response.
+    pub day1_ts: Option<u64>,
+    pub members: Vec<Member>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    pub last_star_ts: u64,
+    /// When each part was solved, by day.
+    pub days: BTreeMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn solved(&self, day: u8) -> [Option<u64>; 2] {
+        self.days.get(&day).copied().unwrap_or_default()
+    }
+}
+
+/// Reads leaderboard `id` from `source`.
+pub fn load(source: &Source, id: u64) -> Result<Leaderboard, String> {
+    let content = match source {
+        Source::File(path) => fs::read_to_string(path)
+            .map_err(|e| format!("could not read \"{}\": {e}", path.display()))?,
+        Source::Url(url) => aoc_client::fetch_url(url).map_err(|e| e.to_string())?,
+        Source::AdventOfCode => fetch_cached(id)?,
+    };
+
+    parse(&content)
+}
+
+fn fetch_cached(id: u64) -> Result<String, String> {
+    let path = PathBuf::from(format!("{CACHE_DIR}/{id}.json"));
+
+    let is_fresh = fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| {
+            SystemTime::now()
+                .duration_since(modified)
+                .is_ok_and(|age| age < CACHE_DURATION)
+        });
+
+    if is_fresh {
+        if let Ok(content) = fs::read_to_string(&path) {
+            return Ok(content);
+        }
+    }
+
+    let content = aoc_client::Client::from_env()
+        .and_then(|client| client.fetch_leaderboard(id))
+        .map_err(|e| e.to_string())?;
+
+    // Advent of Code redirects to the leaderboard page if the session is not a member.
+    if !content.trim_start().starts_with('{') {
+        return Err(format!(
+            "Advent of Code did not return leaderboard {id}, is the account a member of it?"
+        ));
+    }
+
+    fs::create_dir_all(CACHE_DIR).map_err(|e| e.to_string())?;
+    fs::write(&path, &content).map_err(|e| e.to_string())?;
+    Ok(content)
+}
+
+/// Members by local score, with ties going to whoever got their last star first.
+pub fn ranked(leaderboard: &Leaderboard) -> Vec<&Member> {
+    let mut members: Vec<&Member> = leaderboard.members.iter().collect();
+    members.sort_by_key(|m| (std::cmp::Reverse(m.local_score), m.last_star_ts, m.id));
+    members
+}
+
+/// A table of all members with their stars per day, local score and the time of their last star.
+pub fn format_overview(leaderboard: &Leaderboard) -> String {
+    let last_day = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| m.days.keys().last().copied())
+        .max()
+        .unwrap_or(1);
+
+    let days = 1..=last_day;
+    let tens: String = days
+        .clone()
+        .map(|d| {
+            if d < 10 {
+                ' '
+            } else {
+                char::from(b'0' + d / 10)
+            }
+        })
+        .collect();
+    let ones: String = days.clone().map(|d| char::from(b'0' + d % 10)).collect();
+
+    let mut lines = vec![];
+    // only needed from day 10 on.
+    if !tens.trim().is_empty() {
+        lines.push(format!("{:<20}{}", "", tens.trim_end()));
+    }
+    lines.push(format!(
+        "{:>4}  {:>5}  {:>5}  {ones}  {:<16}  Name",
+        "", "Score", "Stars", "Last star (UTC)"
+    ));
+
+    for (rank, member) in ranked(leaderboard).into_iter().enumerate() {
+        let stars: String = days
+            .clone()
+            .map(|day| match member.solved(day) {
+                [Some(_), Some(_)] => '★',
+                [Some(_), None] => '☆',
+                _ => '·',
+            })
+            .collect();
+
+        let last_star = if member.last_star_ts == 0 {
+            "-".to_string()
+        } else {
+            format_timestamp(member.last_star_ts)
+        };
+
+        lines.push(format!(
+            "{:>3})  {:>5}  {:>5}  {stars}  {last_star:<16}  {}",
+            rank + 1,
+            member.local_score,
+            member.stars,
+            member.display_name()
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// A table of the members with stars on `day`, ordered by who finished part two first.
+pub fn format_day(leaderboard: &Leaderboard, day: Day) -> String {
+    let day_number = day.into_inner();
+    let unlock = leaderboard
+        .day1_ts
+        .map(|ts| ts + u64::from(day_number - 1) * 86400);
+
+    let mut members: Vec<(&Member, [Option<u64>; 2])> = leaderboard
+        .members
+        .iter()
+        .map(|m| (m, m.solved(day_number)))
+        .filter(|(_, solved)| solved[0].is_some())
+        .collect();
+
+    if members.is_empty() {
+        return format!("Nobody has a star for day {day} yet.");
+    }
+
+    // members without part two go last.
+    members.sort_by_key(|(m, [part_1, part_2])| (part_2.is_none(), *part_2, *part_1, m.id));
+
+    let cell = |ts: Option<u64>| match (ts, unlock) {
+        (None, _) => "-".to_string(),
+        (Some(ts), Some(unlock)) if ts >= unlock => {
+            format!(
+                "{} (+{})",
+                format_timestamp(ts),
+                format_elapsed(ts - unlock)
+            )
+        }
+        (Some(ts), _) => format_timestamp(ts),
+    };
+
+    let mut lines = vec![format!(
+        "{:>4}  {:<30}  {:<30}  Name",
+        "", "Part 1 (UTC)", "Part 2 (UTC)"
+    )];
+
+    for (rank, (member, [part_1, part_2])) in members.iter().enumerate() {
+        lines.push(format!(
+            "{:>3})  {:<30}  {:<30}  {}",
+            rank + 1,
+            cell(*part_1),
+            cell(*part_2),
+            member.display_name()
+        ));
+    }
+
+    match members.first() {
+        Some((member, [_, Some(_)])) => lines.push(format!(
+            "\n🏆 {} finished part 2 first.",
+            member.display_name()
+        )),
+        _ => lines.push("\nNobody has finished part 2 yet.".to_string()),
+    }
+
+    lines.join("\n")
+}
+
+/// Formats the time since a puzzle unlocked, e.g. `01:02:03`. Hours go past 24.
+fn format_elapsed(seconds: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse(content: &str) -> Result<Leaderboard, String> {
+    let json = JsonValue::from_str(content).or(Err("leaderboard is not valid JSON."))?;
+
+    let json = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected leaderboard to be a JSON object.")?;
+
+    let event = json
+        .get("event")
+        .and_then(|v| v.get::<String>())
+        .ok_or("Expected leaderboard.event to be a string.")?
+        .clone();
+
+    let day1_ts = json.get("day1_ts").and_then(as_timestamp);
+
+    let members = json
+        .get("members")
+        .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+        .ok_or("Expected leaderboard.members to be a JSON object.")?
+        .values()
+        .map(parse_member)
+        .collect::<Result<_, _>>()?;
+
+    Ok(Leaderboard {
+        event,
+        day1_ts,
+        members,
+    })
+}
+
+fn parse_member(value: &JsonValue) -> Result<Member, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected member to be a JSON object.")?;
+
+    let number = |key: &str| {
+        json.get(key)
+            .and_then(as_timestamp)
+            .ok_or(format!("Expected member.{key} to be a number."))
+    };
+
+    let name = match json.get("name") {
+        None | Some(JsonValue::Null) => None,
+        Some(JsonValue::String(name)) => Some(name.clone()),
+        Some(_) => return Err("Expected member.name to be null or a string.".into()),
+    };
+
+    let mut days = BTreeMap::new();
+
+    if let Some(completion) = json.get("completion_day_level") {
+        let completion = completion
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member.completion_day_level to be a JSON object.")?;
+
+        for (day, parts) in completion {
+            let day: u8 = day
+                .parse()
+                .map_err(|_| format!("Expected days to be numbers, got `{day}`."))?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected the parts of a day to be a JSON object.")?;
+
+            let solved_at = |part: &str| {
+                parts
+                    .get(part)
+                    .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                    .and_then(|part| part.get("get_star_ts"))
+                    .and_then(as_timestamp)
+            };
+
+            days.insert(day, [solved_at("1"), solved_at("2")]);
+        }
+    }
+
+    Ok(Member {
+        id: number("id")?,
+        name,
+        local_score: number("local_score")?,
+        stars: number("stars")?,
+        last_star_ts: number("last_star_ts")?,
+        days,
+    })
+}
+
+/// Older responses contain timestamps as strings.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn as_timestamp(value: &JsonValue) -> Option<u64> {
+    match value {
+        JsonValue::Number(n) if *n >= 0.0 => Some(*n as u64),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::day;
+
+    const LEADERBOARD_JSON: &str = include_str!("fixtures/leaderboard.json");
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = parse(LEADERBOARD_JSON).unwrap();
+        assert_eq!(leaderboard.event, "2024");
+        assert_eq!(leaderboard.day1_ts, Some(1_733_029_200));
+
+        let members = ranked(&leaderboard);
+        assert_eq!(
+            members.iter().map(|m| m.display_name()).collect::<Vec<_>>(),
+            vec!["Alice Smith", "(anonymous user #3)", "Bob"]
+        );
+        assert_eq!(
+            members[0].days[&2],
+            [Some(1_733_116_100), Some(1_733_116_400)]
+        );
+        // timestamps as strings.
+        assert_eq!(members[2].days[&1], [Some(1_733_029_800), None]);
+    }
+
+    #[test]
+    fn rejects_malformed_leaderboards() {
+        assert!(parse("<html>").is_err());
+        assert!(parse(r#"{ "event": "2024" }"#).is_err());
+        assert!(parse(r#"{ "event": "2024", "members": { "1": { "id": 1 } } }"#).is_err());
+    }
+
+    #[test]
+    fn formats_overview() {
+        let leaderboard = parse(LEADERBOARD_JSON).unwrap();
+        assert_eq!(
+            format_overview(&leaderboard),
+            [
+                "                             1",
+                "      Score  Stars  1234567890  Last star (UTC)   Name",
+                "  1)     10      4  ★★········  2024-12-02 05:13  Alice Smith",
+                "  2)      6      3  ★☆········  2024-12-02 05:20  (anonymous user #3)",
+                "  3)      3      2  ☆········☆  2024-12-10 05:20  Bob",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn formats_days_by_first_to_finish() {
+        let leaderboard = parse(LEADERBOARD_JSON).unwrap();
+        assert_eq!(
+            format_day(&leaderboard, day!(1)),
+            [
+                "      Part 1 (UTC)                    Part 2 (UTC)                    Name",
+                "  1)  2024-12-01 05:10 (+00:10:00)    2024-12-01 05:30 (+00:30:00)    (anonymous user #3)",
+                "  2)  2024-12-01 05:05 (+00:05:00)    2024-12-01 06:40 (+01:40:00)    Alice Smith",
+                "  3)  2024-12-01 05:10 (+00:10:00)    -                               Bob",
+                "",
+                "🏆 (anonymous user #3) finished part 2 first.",
+            ]
+            .join("\n")
+        );
+
+        assert!(format_day(&leaderboard, day!(10)).ends_with("Nobody has finished part 2 yet."));
+        assert_eq!(
+            format_day(&leaderboard, day!(3)),
+            "Nobody has a star for day 03 yet."
+        );
+    }
+
+    #[test]
+    fn detects_sources() {
+        assert_eq!(Source::new(None, 1), Source::AdventOfCode);
+        assert_eq!(Source::new(Some(" "), 1), Source::AdventOfCode);
+        assert_eq!(
+            Source::new(Some("data/leaderboard-{id}.json"), 42),
+            Source::File(PathBuf::from("data/leaderboard-42.json"))
+        );
+        assert_eq!(
+            Source::new(Some("https://example.com/{id}.json"), 42),
+            Source::Url("https://example.com/42.json".into())
+        );
+    }
+}
@@ -0,0 +1,574 @@
+//! Converts the HTML of a puzzle page to markdown, e.g. for `data/puzzles/01.md`.
+//!
+//! Only the puzzle descriptions (`<article class="day-desc">`) are kept: one for part one and,
+//! once it is unlocked, one for part two. Headings, paragraphs, code blocks, lists, links and
+//! emphasis are converted; other markup is reduced to its text.
+
+/// Elements that never have children or a closing tag.
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+/// Elements whose contents are not HTML.
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// An element whose closing tag was not reached yet: its name, attributes and children so far.
+type OpenElement = (String, Vec<(String, String)>, Vec<Node>);
+
+#[derive(Debug, PartialEq, Eq)]
+enum Node {
+    Element {
+        name: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+}
+
+impl Node {
+    fn attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            Node::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn has_class(&self, class: &str) -> bool {
+        self.attribute("class")
+            .is_some_and(|classes| classes.split_whitespace().any(|c| c == class))
+    }
+}
+
+/// The puzzle descriptions of a puzzle page as markdown.
+#[must_use]
+pub fn from_puzzle_html(html: &str) -> String {
+    let nodes = parse(html);
+
+    let mut articles = vec![];
+    find_articles(&nodes, &mut articles);
+
+    let mut blocks = vec![];
+    for children in articles {
+        render_blocks(children, &mut blocks);
+    }
+
+    let mut markdown = blocks.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// The number of puzzle descriptions on a puzzle page, i.e. 2 once part two is unlocked.
+#[must_use]
+pub fn count_parts(html: &str) -> usize {
+    let nodes = parse(html);
+
+    let mut articles = vec![];
+    find_articles(&nodes, &mut articles);
+    articles.len()
+}
+
+fn find_articles<'a>(nodes: &'a [Node], articles: &mut Vec<&'a [Node]>) {
+    for node in nodes {
+        if let Node::Element { name, children, .. } = node {
+            if name == "article" && node.has_class("day-desc") {
+                articles.push(children);
+            } else {
+                find_articles(children, articles);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                   Parsing                                  */
+/* -------------------------------------------------------------------------- */
+
+/// Parses HTML into a tree. Unclosed elements are closed by the closing tag of their parent.
+fn parse(html: &str) -> Vec<Node> {
+    // elements that are still open, with their children so far. The first entry is the root.
+    let mut stack: Vec<OpenElement> = vec![(String::new(), vec![], vec![])];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.split_once("-->").map_or("", |(_, after)| after);
+        } else if rest.starts_with("<!") {
+            rest = rest.split_once('>').map_or("", |(_, after)| after);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let (name, after) = tag.split_once('>').unwrap_or((tag, ""));
+            close(&mut stack, &name.trim().to_lowercase());
+            rest = after;
+        } else {
+            let (tag, after) = rest[1..].split_once('>').unwrap_or((&rest[1..], ""));
+            let (name, attributes) = parse_tag(tag.trim_end_matches('/'));
+
+            if name.is_empty() {
+                // not a tag, e.g. `a < b`.
+                push_text(&mut stack, "<");
+                rest = &rest[1..];
+                continue;
+            }
+
+            rest = after;
+
+            if VOID_ELEMENTS.contains(&name.as_str()) {
+                push_node(
+                    &mut stack,
+                    Node::Element {
+                        name,
+                        attributes,
+                        children: vec![],
+                    },
+                );
+            } else if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                // skip the contents, they might contain `<` that does not start a tag.
+                let end = format!("</{name}");
+                rest = rest
+                    .find(&end)
+                    .and_then(|i| rest[i..].split_once('>'))
+                    .map_or("", |(_, after)| after);
+            } else {
+                stack.push((name, attributes, vec![]));
+            }
+        }
+    }
+
+    while stack.len() > 1 {
+        close_last(&mut stack);
+    }
+
+    stack.pop().map(|(_, _, nodes)| nodes).unwrap_or_default()
+}
+
+/// Splits `tag name="value" other` into its lower-cased name and attributes.
+fn parse_tag(tag: &str) -> (String, Vec<(String, String)>) {
+    let (name, mut rest) = tag
+        .split_once(|c: char| c.is_ascii_whitespace())
+        .unwrap_or((tag, ""));
+
+    let name = name.to_lowercase();
+    if !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return (String::new(), vec![]);
+    }
+
+    let mut attributes = vec![];
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'');
+
+            let (value, after) = match quote {
+                Some(quote) => value[1..].split_once(quote).unwrap_or((&value[1..], "")),
+                None => value
+                    .split_once(|c: char| c.is_ascii_whitespace())
+                    .unwrap_or((value, "")),
+            };
+            rest = after;
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+
+        attributes.push((key, value));
+    }
+
+    (name, attributes)
+}
+
+fn push_node(stack: &mut [OpenElement], node: Node) {
+    stack.last_mut().unwrap().2.push(node);
+}
+
+fn push_text(stack: &mut [OpenElement], text: &str) {
+    if !text.is_empty() {
+        push_node(stack, Node::Text(decode_entities(text)));
+    }
+}
+
+/// Closes the innermost element named `name`, and every element opened within it. Stray closing tags are ignored.
+fn close(stack: &mut Vec<OpenElement>, name: &str) {
+    if let Some(index) = stack.iter().skip(1).rposition(|(n, _, _)| n == name) {
+        while stack.len() > index + 1 {
+            close_last(stack);
+        }
+    }
+}
+
+fn close_last(stack: &mut Vec<OpenElement>) {
+    let (name, attributes, children) = stack.pop().unwrap();
+    push_node(
+        stack,
+        Node::Element {
+            name,
+            attributes,
+            children,
+        },
+    );
+}
+
+/// Replaces character references like `&lt;` or `&#39;` with the characters they stand for.
+#[must_use]
+pub fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let reference = rest[1..]
+            .split_once(';')
+            .filter(|(name, _)| name.len() <= 8)
+            .and_then(|(name, after)| Some((decode_entity(name)?, after)));
+
+        match reference {
+            Some((c, after)) => {
+                decoded.push(c);
+                rest = after;
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let code = match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                  Rendering                                 */
+/* -------------------------------------------------------------------------- */
+
+/// Renders block-level nodes, adding one entry per paragraph, heading, code block or list.
+fn render_blocks(nodes: &[Node], blocks: &mut Vec<String>) {
+    let mut inline: Vec<&Node> = vec![];
+
+    for node in nodes {
+        let Node::Element { name, children, .. } = node else {
+            inline.push(node);
+            continue;
+        };
+
+        if !is_block(name) {
+            inline.push(node);
+            continue;
+        }
+
+        flush_inline(&mut inline, blocks);
+
+        match name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse().unwrap_or(2);
+                blocks.push(format!("{} {}", "#".repeat(level), render_inline(children)));
+            }
+            "p" => {
+                let text = render_inline(children);
+                if !text.is_empty() {
+                    blocks.push(text);
+                }
+            }
+            "pre" => {
+                let mut code = text_content(children);
+                if code.ends_with('\n') {
+                    code.pop();
+                }
+                blocks.push(format!("```\n{code}\n```"));
+            }
+            "ul" | "ol" => blocks.push(render_list(node, 0)),
+            "blockquote" => {
+                let mut quoted = vec![];
+                render_blocks(children, &mut quoted);
+                blocks.push(
+                    quoted
+                        .join("\n\n")
+                        .lines()
+                        .map(|line| format!("> {line}").trim_end().to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+            }
+            _ => render_blocks(children, blocks),
+        }
+    }
+
+    flush_inline(&mut inline, blocks);
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "h1" | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "p"
+            | "pre"
+            | "ul"
+            | "ol"
+            | "blockquote"
+            | "div"
+            | "section"
+            | "article"
+    )
+}
+
+/// Text between blocks is rendered as a paragraph of its own.
+fn flush_inline(inline: &mut Vec<&Node>, blocks: &mut Vec<String>) {
+    if inline.is_empty() {
+        return;
+    }
+
+    let text = render_inline_nodes(inline.drain(..));
+    if !text.is_empty() {
+        blocks.push(text);
+    }
+}
+
+/// Renders a list with its nested lists, indented by two spaces per level.
+fn render_list(list: &Node, depth: usize) -> String {
+    let Node::Element { name, children, .. } = list else {
+        return String::new();
+    };
+
+    let indent = "  ".repeat(depth);
+    let mut lines = vec![];
+    let mut number = 0;
+
+    for item in children {
+        let Node::Element {
+            name: item_name,
+            children: item_children,
+            ..
+        } = item
+        else {
+            continue;
+        };
+
+        if item_name != "li" {
+            continue;
+        }
+
+        number += 1;
+        let marker = if name == "ol" {
+            format!("{number}.")
+        } else {
+            "-".to_string()
+        };
+
+        let (nested, inline): (Vec<&Node>, Vec<&Node>) = item_children
+            .iter()
+            .partition(|n| matches!(n, Node::Element { name, .. } if name == "ul" || name == "ol"));
+
+        lines.push(format!(
+            "{indent}{marker} {}",
+            render_inline_nodes(inline.into_iter())
+        ));
+
+        for list in nested {
+            lines.push(render_list(list, depth + 1));
+        }
+    }
+
+    lines.join("\n")
+}
+
+fn render_inline(nodes: &[Node]) -> String {
+    render_inline_nodes(nodes.iter())
+}
+
+/// Renders inline nodes to a single line with collapsed whitespace.
+fn render_inline_nodes<'a>(nodes: impl Iterator<Item = &'a Node>) -> String {
+    let text: String = nodes.map(|node| render_inline_node(node, false)).collect();
+    text.split(' ')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn render_inline_node(node: &Node, in_code: bool) -> String {
+    match node {
+        Node::Text(text) => {
+            let text = collapse_whitespace(text);
+            if in_code {
+                text
+            } else {
+                escape(&text)
+            }
+        }
+        Node::Element { name, children, .. } => {
+            let inner: String = children
+                .iter()
+                .map(|child| render_inline_node(child, in_code || name == "code"))
+                .collect();
+
+            match name.as_str() {
+                "em" | "i" | "strong" | "b" => wrap(&inner, "*", "*"),
+                "code" if !in_code => wrap(&inner, "`", "`"),
+                "a" => match node.attribute("href") {
+                    Some(href) => wrap(&inner, "[", &format!("]({href})")),
+                    None => inner,
+                },
+                "br" => " ".into(),
+                _ => inner,
+            }
+        }
+    }
+}
+
+/// Wraps `text` in markers, keeping surrounding whitespace outside of them, since e.g. `* a*` is no emphasis.
+fn wrap(text: &str, open: &str, close: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+
+    let leading = &text[..text.len() - text.trim_start().len()];
+    let trailing = &text[text.trim_end().len()..];
+    format!("{leading}{open}{trimmed}{close}{trailing}")
+}
+
+/// The text of nodes as-is, e.g. for code blocks.
+fn text_content(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => text_content(children),
+        })
+        .collect()
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut in_space = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                collapsed.push(' ');
+            }
+            in_space = true;
+        } else {
+            collapsed.push(c);
+            in_space = false;
+        }
+    }
+
+    collapsed
+}
+
+/// Escapes characters that markdown would read as markup.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+    use crate::template::puzzle;
+
+    const PUZZLE_HTML: &str = include_str!("fixtures/puzzle.html");
+    const PUZZLE_MARKDOWN: &str = include_str!("fixtures/puzzle_converted.md");
+
+    #[test]
+    fn converts_puzzle_pages() {
+        assert_eq!(from_puzzle_html(PUZZLE_HTML), PUZZLE_MARKDOWN);
+    }
+
+    #[test]
+    fn converts_to_parsable_descriptions() {
+        let sections = puzzle::parse(&from_puzzle_html(PUZZLE_HTML));
+        assert_eq!(sections, puzzle::parse(include_str!("fixtures/puzzle.md")));
+    }
+
+    #[test]
+    fn converts_first_part() {
+        let (part_one, _) = PUZZLE_HTML.split_once("<p>Your puzzle answer").unwrap();
+        let (expected, _) = PUZZLE_MARKDOWN
+            .split_once("\n\n## --- Part Two ---")
+            .unwrap();
+        assert_eq!(from_puzzle_html(part_one), format!("{expected}\n"));
+
+        assert_eq!(count_parts(part_one), 1);
+        assert_eq!(count_parts(PUZZLE_HTML), 2);
+    }
+
+    #[test]
+    fn converts_inline_markup() {
+        let html = r#"<article class="day-desc"><p>A <em> loud </em>
+            <a href="/2024/day/1/input">link</a>, <code>a*b</code>, <em><code>42</code></em> &amp; 2*3_4</p></article>"#;
+        assert_eq!(
+            from_puzzle_html(html),
+            "A *loud* [link](/2024/day/1/input), `a*b`, *`42`* & 2\\*3\\_4\n"
+        );
+    }
+
+    #[test]
+    fn converts_ordered_lists() {
+        let html = r#"<article class="day-desc"><ol><li>one</li><li>two<ol><li>three</li></ol></li></ol></article>"#;
+        assert_eq!(from_puzzle_html(html), "1. one\n2. two\n  1. three\n");
+    }
+
+    #[test]
+    fn keeps_code_blocks_as_is() {
+        let html = "<article class=\"day-desc\"><pre><code>a  &lt;b&gt;\n  <em>*c*</em>\n\n</code></pre></article>";
+        assert_eq!(from_puzzle_html(html), "```\na  <b>\n  *c*\n\n```\n");
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("&lt;&#39;&#x41;&quot;&gt; &amp;amp; & &unknown; a&b"),
+            "<'A\"> &amp; & &unknown; a&b"
+        );
+    }
+}
@@ -1,14 +1,33 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
+pub mod bench_settings;
 pub mod commands;
+pub mod examples;
+pub mod fuzz;
+pub mod inputs;
+pub mod registry;
 pub mod runner;
+pub mod timeouts;
 
 pub use day::*;
 
+mod accounts;
+mod answers;
 mod day;
+mod heap_stats;
+mod history;
+mod leaderboard;
+mod markdown;
+mod panics;
+mod prompt;
+mod puzzle;
+mod rate_limit;
 mod readme_benchmarks;
+mod results;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -36,22 +55,100 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
+/// Stands in for `env::var` in tests of `from_vars` functions, looking up `key` in `vars`.
+#[cfg(feature = "test_lib")]
+pub(crate) fn test_vars<'a>(vars: &'a [(&str, &str)]) -> impl Fn(&str) -> Option<String> + 'a {
+    |key| {
+        vars.iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| (*value).to_string())
+    }
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Pass `parse = <function>` to parse the input once: both parts then receive a reference to its
+/// output instead of the raw input, and parsing is timed as its own phase. The function returns an
+/// `Option` or a `Result`, if parsing fails both parts are skipped.
+/// Pass `impls = { part_one: [name = <function>], part_two: [...] }` to register alternative
+/// implementations of a part, which can be picked with `--impl <name>` or compared with `--compare`.
+/// The generated `SOLUTION` constant registers the day with the `dispatcher` binary.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_one, 1, []] [part_two, 2, []]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [part_one, 1, []]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_two, 2, []]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, [$parse], [part_one, 1, []] [part_two, 2, []]);
     };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl $day, [$parse], [part_one, 1, []]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl $day, [$parse], [part_two, 2, []]);
+    };
+    (
+        $day:expr, $(parse = $parse:expr,)? impls = {
+            $(part_one: [$($name_one:ident = $func_one:expr),* $(,)?] $(,)?)?
+            $(part_two: [$($name_two:ident = $func_two:expr),* $(,)?] $(,)?)?
+        }
+    ) => {
+        $crate::solution!(
+            @impl $day,
+            [$($parse)?],
+            [part_one, 1, [$($($name_one = $func_one),*)?]]
+            [part_two, 2, [$($($name_two = $func_two),*)?]]
+        );
+    };
+
+    (@impl $day:expr, [], $( [$func:expr, $part:expr, [$($name:ident = $alt:expr),*]] )*) => {
+        $crate::solution!(@common $day);
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        pub fn main() {
+            use $crate::template::runner::*;
+            let Some(input) = $crate::template::inputs::read_or_report(DAY) else {
+                return;
+            };
+            $( $crate::solution!(@run input.as_str(), $func, $part, [$($name = $alt),*]); )*
+        }
+    };
+
+    (@impl $day:expr, [$parse:expr], $( [$func:expr, $part:expr, [$($name:ident = $alt:expr),*]] )*) => {
+        $crate::solution!(@common $day);
+
+        pub fn main() {
+            use $crate::template::runner::*;
+            let Some(input) = $crate::template::inputs::read_or_report(DAY) else {
+                return;
+            };
+            let Some(parsed) = run_parse($parse, input.as_str()) else {
+                $( skip_part($part); )*
+                return;
+            };
+            $( $crate::solution!(@run &parsed, $func, $part, [$($name = $alt),*]); )*
+        }
+    };
+
+    (@run $input:expr, $func:expr, $part:expr, [$($name:ident = $alt:expr),*]) => {
+        run_implementations(
+            &[
+                (DEFAULT_IMPLEMENTATION, |input| IntoAnswer::into_answer($func(input))),
+                $( (stringify!($name), |input| IntoAnswer::into_answer($alt(input))), )*
+            ],
+            $input,
+            DAY,
+            $part,
+        )
+    };
+
+    (@common $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +156,9 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        /// Registers this day with the `dispatcher` binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution { day: DAY, run: main };
     };
 }
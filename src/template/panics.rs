@@ -0,0 +1,109 @@
+/// Catches panics of solution parts, so that one failing part does not take down the others.
+use std::{
+    any::Any,
+    backtrace::{Backtrace, BacktraceStatus},
+    cell::RefCell,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// Where and why a part panicked.
+#[derive(Debug)]
+pub struct PanicReport {
+    pub location: Option<String>,
+    pub message: String,
+    /// Only captured if enabled with `RUST_BACKTRACE=1`.
+    pub backtrace: Backtrace,
+}
+
+impl PanicReport {
+    /// The backtrace, if one was captured.
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        (self.backtrace.status() == BacktraceStatus::Captured).then_some(&self.backtrace)
+    }
+}
+
+impl Display for PanicReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<PanicReport>> = const { RefCell::new(None) };
+}
+
+/// Runs `func` and returns a report instead of unwinding if it panics.
+/// While `func` runs, the default panic message is replaced by the report.
+pub fn catch<R>(func: impl FnOnce() -> R) -> Result<R, PanicReport> {
+    let previous_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(|info| {
+        let report = PanicReport {
+            location: info.location().map(ToString::to_string),
+            message: payload_message(info.payload()),
+            backtrace: Backtrace::capture(),
+        };
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some(report));
+    }));
+
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    panic::set_hook(previous_hook);
+
+    result.map_err(|payload| {
+        LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_else(|| PanicReport {
+                location: None,
+                message: payload_message(payload.as_ref()),
+                backtrace: Backtrace::disabled(),
+            })
+    })
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".into())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::catch;
+
+    #[test]
+    fn passes_results_through() {
+        assert_eq!(catch(|| 42).unwrap(), 42);
+    }
+
+    #[test]
+    fn reports_location_and_message() {
+        let items: Vec<u8> = vec![];
+        let index = items.len() + 2;
+
+        let report = catch(|| items[index]).unwrap_err();
+
+        assert!(report
+            .location
+            .as_deref()
+            .is_some_and(|l| l.starts_with("src/template/panics.rs:")));
+        assert_eq!(
+            report.message,
+            "index out of bounds: the len is 0 but the index is 2"
+        );
+        assert!(report
+            .to_string()
+            .starts_with("panicked at src/template/panics.rs:"));
+    }
+
+    #[test]
+    fn reports_formatted_messages() {
+        let report = catch(|| panic!("day {} is broken", 4)).unwrap_err();
+        assert_eq!(report.message, "day 4 is broken");
+    }
+}
@@ -0,0 +1,46 @@
+/// Asks the user questions on the terminal.
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    process::Command,
+};
+
+/// Reads one line from stdin. Returns an empty string at the end of input, so that defaults apply.
+pub fn line(question: &str) -> String {
+    print!("{question}");
+    let _ = io::stdout().flush();
+
+    let mut input = String::new();
+    if io::stdin().lock().read_line(&mut input).unwrap_or(0) == 0 {
+        println!();
+    }
+
+    input.trim().to_string()
+}
+
+/// Same as [`line`], but the input is not echoed, e.g. for session cookies.
+/// Echo is turned off with `stty`. Where that is not available, e.g. on Windows, the input is shown.
+pub fn hidden(question: &str) -> String {
+    let set_echo = |is_on: bool| {
+        io::stdin().is_terminal()
+            && Command::new("stty")
+                .arg(if is_on { "echo" } else { "-echo" })
+                .status()
+                .is_ok_and(|status| status.success())
+    };
+
+    let is_hidden = set_echo(false);
+    let input = line(question);
+
+    if is_hidden {
+        set_echo(true);
+        // the line break typed by the user was not echoed either.
+        println!();
+    }
+
+    input
+}
+
+/// Asks a yes/no question. Anything but `y` or `yes` counts as no.
+pub fn confirm(question: &str) -> bool {
+    matches!(line(question).to_lowercase().as_str(), "y" | "yes")
+}
@@ -0,0 +1,172 @@
+/// Parses the puzzle descriptions written by `cargo download`, e.g. `data/puzzles/01.md`, for example inputs and their answers.
+use std::path::{Path, PathBuf};
+
+use crate::template::Day;
+
+/// Heading that starts the second part of a puzzle description.
+const PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// Phrases that usually lead up to the answer of an example, e.g. "Adding these together produces `*142*`".
+const ANSWER_PHRASES: [&str; 10] = [
+    "answer",
+    "total",
+    "sum",
+    "result",
+    "produces",
+    "score",
+    "count",
+    "number of",
+    "product",
+    "would be",
+];
+
+/// The text of one part of a puzzle.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Section {
+    /// Contents of the code blocks of the part, in order of appearance.
+    pub blocks: Vec<String>,
+    /// Candidate answers of the example, in order of appearance. The final answer usually comes last.
+    pub answers: Vec<String>,
+}
+
+pub fn get_path(day: Day) -> PathBuf {
+    Path::new("data").join("puzzles").join(format!("{day}.md"))
+}
+
+/// Splits a puzzle description into its parts. The second part is only present after solving the first one.
+pub fn parse(markdown: &str) -> Vec<Section> {
+    let mut sections: Vec<(Vec<String>, Vec<&str>)> = vec![(vec![], vec![])];
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(lines) => sections.last_mut().unwrap().0.push(lines.join("\n")),
+                None => block = Some(vec![]),
+            }
+        } else if let Some(lines) = block.as_mut() {
+            lines.push(line);
+        } else if line.contains(PART_TWO_HEADING) {
+            sections.push((vec![], vec![]));
+        } else {
+            sections.last_mut().unwrap().1.push(line);
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(blocks, prose)| Section {
+            blocks,
+            answers: find_answers(&prose.join("\n")),
+        })
+        .collect()
+}
+
+/// Collects emphasized code spans that follow answer phrasing in the same sentence.
+/// Falls back to every emphasized code span if none does.
+fn find_answers(prose: &str) -> Vec<String> {
+    let spans: Vec<(String, bool)> = prose
+        .split("\n\n")
+        .flat_map(|paragraph| {
+            emphasized_code_spans(paragraph)
+                .into_iter()
+                .map(|(index, value)| (value, follows_answer_phrase(&paragraph[..index])))
+        })
+        .filter(|(value, _)| !value.is_empty() && !value.contains(char::is_whitespace))
+        .collect();
+
+    let has_phrased = spans.iter().any(|(_, is_phrased)| *is_phrased);
+
+    spans
+        .into_iter()
+        .filter(|(_, is_phrased)| *is_phrased || !has_phrased)
+        .map(|(value, _)| value)
+        .collect()
+}
+
+fn follows_answer_phrase(preceding: &str) -> bool {
+    let start = [". ", "! ", "? "]
+        .iter()
+        .filter_map(|end| preceding.rfind(end).map(|i| i + end.len()))
+        .max()
+        .unwrap_or(0);
+    let sentence = preceding[start..].to_lowercase();
+
+    ANSWER_PHRASES
+        .iter()
+        .any(|phrase| sentence.contains(phrase))
+}
+
+/// Finds code spans that are emphasized, written as either `` `*42*` `` or `` *`42`* ``.
+/// Returns the byte index of each span in `text` together with its value.
+fn emphasized_code_spans(text: &str) -> Vec<(usize, String)> {
+    let mut spans = vec![];
+    let mut rest = 0;
+
+    while let Some(start) = text[rest..].find('`').map(|i| rest + i) {
+        let Some(end) = text[start + 1..].find('`').map(|i| start + 1 + i) else {
+            break;
+        };
+
+        let content = &text[start + 1..end];
+        let is_emphasized =
+            (content.len() > 2 && content.starts_with('*') && content.ends_with('*'))
+                || (text[..start].ends_with('*') && text[end + 1..].starts_with('*'));
+
+        if is_emphasized {
+            spans.push((start, content.trim_matches('*').trim().to_string()));
+        }
+
+        rest = end + 1;
+    }
+
+    spans
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasized_code_spans, parse, Section};
+
+    const PUZZLE: &str = include_str!("fixtures/puzzle.md");
+
+    #[test]
+    fn finds_emphasized_code_spans() {
+        assert_eq!(
+            emphasized_code_spans("a `*12*` and *`34`*, but not `56` or *78*"),
+            vec![(2, "12".into()), (14, "34".into())]
+        );
+    }
+
+    #[test]
+    fn parses_parts() {
+        let sections = parse(PUZZLE);
+
+        assert_eq!(
+            sections,
+            vec![
+                Section {
+                    blocks: vec!["1 2\n3 4\n5 6".into(), "1 + 2 = 3".into()],
+                    answers: vec!["21".into()],
+                },
+                Section {
+                    blocks: vec!["7\n8\n9".into()],
+                    answers: vec!["2".into(), "24".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_first_part() {
+        let (part_one, _) = PUZZLE.split_once("--- Part Two ---").unwrap();
+        assert_eq!(parse(part_one).len(), 1);
+    }
+
+    #[test]
+    fn falls_back_to_all_emphasized_code_spans() {
+        let sections = parse("It was `*12*`, then `*34*` and `*5 6*`.");
+        assert_eq!(sections[0].answers, vec!["12", "34"]);
+    }
+}
@@ -0,0 +1,193 @@
+/// Spaces out requests to Advent of Code, so that bulk operations like downloading many days do not hammer the site.
+///
+/// The time of the last request is shared through a file in the temp directory, so the limit also holds across
+/// processes, e.g. when running `cargo download` in a shell loop. Processes take turns by locking the file while
+/// they wait, so even ones started at the same time do not send their requests together.
+use std::{
+    env,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, PoisonError},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+const LAST_REQUEST_FILE: &str = "advent-of-code-last-request";
+
+pub struct RateLimiter {
+    interval: Duration,
+    /// Where the time of the last request is shared with other processes, if anywhere.
+    shared_path: Option<PathBuf>,
+    last_request: Mutex<Option<SystemTime>>,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration, shared_path: Option<PathBuf>) -> Self {
+        Self {
+            interval,
+            shared_path,
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Limits requests of all processes of the current user.
+    pub fn shared(interval: Duration) -> Self {
+        Self::new(interval, Some(env::temp_dir().join(LAST_REQUEST_FILE)))
+    }
+
+    /// Blocks until `interval` has passed since the last request, then records a new one.
+    pub fn wait(&self) {
+        let mut last_request = self
+            .last_request
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        // held until the new request is recorded. If the file cannot be locked, only this process is limited.
+        let mut shared_file = self.shared_path.as_deref().and_then(lock);
+        let shared = shared_file.as_mut().and_then(read_time);
+
+        if let Some(delay) = (*last_request)
+            .max(shared)
+            .and_then(|previous| delay(previous, SystemTime::now(), self.interval))
+        {
+            thread::sleep(delay);
+        }
+
+        let now = SystemTime::now();
+        *last_request = Some(now);
+
+        if let Some(file) = &mut shared_file {
+            // if the time cannot be shared, other processes merely do not wait for this one.
+            let _ = write_time(file, now);
+        }
+    }
+}
+
+impl ureq::Middleware for RateLimiter {
+    fn handle(
+        &self,
+        request: ureq::Request,
+        next: ureq::MiddlewareNext,
+    ) -> Result<ureq::Response, ureq::Error> {
+        self.wait();
+        next.handle(request)
+    }
+}
+
+/// How long to wait before the next request, if at all.
+fn delay(previous: SystemTime, now: SystemTime, interval: Duration) -> Option<Duration> {
+    // a previous request in the future means the clock was turned back, so wait the full interval.
+    let elapsed = now.duration_since(previous).unwrap_or(Duration::ZERO);
+    interval
+        .checked_sub(elapsed)
+        .filter(|delay| !delay.is_zero())
+}
+
+/// Opens the shared file and waits until no other process holds it. The lock is released when it is closed.
+fn lock(path: &Path) -> Option<File> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
+        .ok()?;
+    file.lock().ok()?;
+    Some(file)
+}
+
+fn read_time(file: &mut File) -> Option<SystemTime> {
+    let mut content = String::new();
+    file.read_to_string(&mut content).ok()?;
+    let millis = content.trim().parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+fn write_time(file: &mut File, time: SystemTime) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(to_millis(time).to_string().as_bytes())
+}
+
+fn to_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, process, sync::Barrier, time::Instant};
+
+    use super::*;
+
+    #[test]
+    fn waits_for_the_rest_of_the_interval() {
+        let previous = UNIX_EPOCH + Duration::from_secs(100);
+        let interval = Duration::from_secs(3);
+
+        assert_eq!(
+            delay(previous, previous + Duration::from_secs(1), interval),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            delay(previous, previous + Duration::from_secs(3), interval),
+            None
+        );
+        assert_eq!(
+            delay(previous, previous + Duration::from_secs(60), interval),
+            None
+        );
+        assert_eq!(
+            delay(previous, previous - Duration::from_secs(1), interval),
+            Some(interval)
+        );
+    }
+
+    #[test]
+    fn shares_last_request_between_limiters() {
+        let path = env::temp_dir().join(format!("{LAST_REQUEST_FILE}-test-{}", process::id()));
+        let interval = Duration::from_millis(200);
+
+        let first = RateLimiter::new(interval, Some(path.clone()));
+        let second = RateLimiter::new(interval, Some(path.clone()));
+
+        let start = Instant::now();
+        first.wait();
+        assert!(start.elapsed() < interval);
+
+        second.wait();
+        assert!(start.elapsed() >= Duration::from_millis(150));
+
+        let _ = fs::remove_file(path);
+    }
+
+    #[test]
+    fn takes_turns_when_waiting_at_the_same_time() {
+        let path = env::temp_dir().join(format!("{LAST_REQUEST_FILE}-turns-{}", process::id()));
+        let interval = Duration::from_millis(100);
+        let barrier = Barrier::new(3);
+
+        let mut requests: Vec<SystemTime> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..3)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let limiter = RateLimiter::new(interval, Some(path.clone()));
+                        barrier.wait();
+                        limiter.wait();
+                        let request = *limiter.last_request.lock().unwrap();
+                        request.unwrap()
+                    })
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        requests.sort();
+
+        for pair in requests.windows(2) {
+            assert!(pair[1].duration_since(pair[0]).unwrap() >= Duration::from_millis(98));
+        }
+
+        let _ = fs::remove_file(path);
+    }
+}
@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::heap_stats::format_bytes;
 use crate::template::timings::Timings;
 use crate::template::Day;
 
@@ -55,22 +56,53 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // only show a parse column if at least one solution has a separate parse phase.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    // only show heap columns if at least one solution was profiled with `--memory`.
+    let has_memory = timings.data.iter().any(|t| t.memory.is_some());
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_memory {
+        columns.extend(["Total heap", "Peak heap", "Allocations"]);
+    }
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!(
+        "|{} |",
+        " :---: |".repeat(columns.len() - 1) + " :---: "
+    ));
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        let parse = if has_parse {
+            format!(" `{}` |", timing.parse.unwrap_or_else(|| "-".into()))
+        } else {
+            String::new()
+        };
+        let memory = match (has_memory, timing.memory) {
+            (false, _) => String::new(),
+            (true, None) => " `-` | `-` | `-` |".into(),
+            (true, Some(stats)) => format!(
+                " `{}` | `{}` | `{}` |",
+                format_bytes(stats.total_bytes),
+                format_bytes(stats.peak_bytes),
+                stats.allocations
+            ),
+        };
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} `{}` | `{}` |{}",
             timing.day.into_inner(),
             path,
+            parse,
             timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.part_2.unwrap_or_else(|| "-".into()),
+            memory
         ));
     }
 
@@ -100,27 +132,47 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::heap_stats::HeapStats, template::timings::Timing, template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -180,4 +232,54 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].memory = Some(HeapStats {
+            total_bytes: 2048,
+            peak_bytes: 512,
+            allocations: 12,
+        });
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 | Total heap | Peak heap | Allocations |",
+            "| :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `2.0 KiB` | `512 B` | `12` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `-` | `-` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `-` | `-` | `-` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
 }
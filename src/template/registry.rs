@@ -0,0 +1,18 @@
+/// Registry of solutions that can be run in-process by the `dispatcher` binary.
+///
+/// Every solution registers itself through the `SOLUTION` constant generated by `solution!`.
+/// `build.rs` collects these constants from all `src/bin/DD.rs` files into a single list.
+use crate::template::Day;
+
+/// A solution that can be called in-process.
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    pub day: Day,
+    /// Runs the solution for its real input, same as the `main` of its own binary.
+    pub run: fn(),
+}
+
+/// Looks up the solution for `day` in a set of registered solutions.
+pub fn find(solutions: &[Solution], day: Day) -> Option<&Solution> {
+    solutions.iter().find(|s| s.day == day)
+}
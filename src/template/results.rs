@@ -0,0 +1,245 @@
+/// Machine-readable results that solution binaries report back to `run_multi`.
+///
+/// When the `AOC_RESULTS_FILE` environment variable is set, every part appends one JSON object
+/// per line to that file. This keeps the protocol independent of whatever a solution prints.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::stats::Summary;
+
+pub const RESULTS_FILE_ENV: &str = "AOC_RESULTS_FILE";
+
+/// Results of the shared parse phase are reported as part `0`.
+pub const PARSE_PART: u8 = 0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+    /// The part panicked.
+    Panicked,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+            Status::Panicked => "panicked",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "failed" => Ok(Status::Failed),
+            "panicked" => Ok(Status::Panicked),
+            x => Err(format!("Unknown result status `{x}`.")),
+        }
+    }
+}
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+    /// Error text of a failed or panicked part.
+    pub error: Option<String>,
+    pub summary: Summary,
+}
+
+/// Appends `result` to the results file, if one was requested by the parent process.
+pub fn emit(result: &PartResult) -> Result<(), io::Error> {
+    let Ok(path) = env::var(RESULTS_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = JsonValue::from(result)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all results from a results file. A missing file yields no results.
+pub fn read_file(path: &Path) -> Result<Vec<PartResult>, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parses JSON-lines content into results.
+pub fn parse(content: &str) -> Result<Vec<PartResult>, String> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err(format!("not a valid JSON line: {l}")))?;
+            PartResult::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.summary.mean.as_nanos() as f64),
+        );
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.summary.samples as f64),
+        );
+        map.insert("stats".into(), JsonValue::from(&value.summary));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected result.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?
+            .cloned();
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected result.status to be a string.")?
+            .parse()?;
+
+        let error = match json.get("error") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(error)) => Some(error.clone()),
+            Some(_) => return Err("Expected result.error to be null or string.".into()),
+        };
+
+        let summary = json
+            .get("stats")
+            .ok_or("Expected result to have key `stats`.")
+            .map(Summary::try_from)??;
+
+        Ok(PartResult {
+            part,
+            answer,
+            status,
+            error,
+            summary,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, PartResult, Status};
+    use crate::template::stats::Summary;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_results() {
+        let result = PartResult {
+            part: 2,
+            answer: Some("11387".into()),
+            status: Status::Solved,
+            error: None,
+            summary: Summary::from_samples(&[Duration::from_nanos(10), Duration::from_nanos(20)]),
+        };
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(parse(&line).unwrap(), vec![result]);
+    }
+
+    #[test]
+    fn roundtrips_failed_results() {
+        let result = PartResult {
+            part: 1,
+            answer: None,
+            status: Status::Failed,
+            error: Some("invalid digit found in string".into()),
+            summary: Summary::from_samples(&[Duration::from_nanos(10)]),
+        };
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert!(line.contains(r#""error":"invalid digit found in string""#));
+        assert_eq!(parse(&line).unwrap(), vec![result]);
+    }
+
+    #[test]
+    fn parses_multiple_lines() {
+        let content = [
+            r#"{"part":1,"answer":"42","status":"solved","duration_nanos":74,"samples":1,"stats":{"samples":1,"outliers":0,"mean_nanos":74,"std_dev_nanos":0,"min_nanos":74,"median_nanos":74,"p95_nanos":74,"max_nanos":74}}"#,
+            "",
+            r#"{"part":2,"answer":null,"status":"unsolved","duration_nanos":12,"samples":1,"stats":{"samples":1,"outliers":0,"mean_nanos":12,"std_dev_nanos":0,"min_nanos":12,"median_nanos":12,"p95_nanos":12,"max_nanos":12}}"#,
+        ]
+        .join("\n");
+
+        let results = parse(&content).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].answer, Some("42".into()));
+        assert_eq!(results[0].summary.mean, Duration::from_nanos(74));
+        assert_eq!(results[1].status, Status::Unsolved);
+        assert_eq!(results[1].answer, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_lines() {
+        parse(r#"{"part":1}"#).unwrap();
+    }
+}
@@ -1,38 +1,155 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{bench_settings::BenchSettings, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    heap_stats::{format_bytes, HeapStats},
+    inputs,
+    results::{PartResult, Status, PARSE_PART},
+    timeouts::Timeouts,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    is_memory: bool,
+    jobs: usize,
+    settings: &BenchSettings,
+    timeouts: &Timeouts,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut timed_out: Vec<Day> = vec![];
+    let mut failed: Vec<Day> = vec![];
+    let mut panicked: Vec<String> = vec![];
 
     let mut need_space = false;
 
+    let mut print_header = |day: Day| {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
+
+    let dispatcher = child_commands::build_dispatcher(is_release);
+
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    // check inputs up front, so that offers to download them do not interleave with output of running days.
+    let (days, missing_input): (Vec<Day>, Vec<Day>) = days
+        .into_iter()
+        .partition(|day| !Path::new(&get_path_for_bin(*day)).exists() || inputs::ensure(*day));
+
+    // timed runs stay serial, so that days do not compete for CPU time.
+    if !is_timed && jobs > 1 {
+        run_parallel(
+            &days,
+            jobs,
+            |day| {
+                child_commands::run_solution_buffered(
+                    day,
+                    is_release,
+                    settings,
+                    dispatcher.as_deref(),
+                    timeouts.for_day(day),
+                )
+            },
+            |day, run| {
+                print_header(day);
+
+                let run = match run {
+                    Ok(run) => run,
+                    Err(e) => {
+                        println!("Failed to run solution: {e:?}");
+                        failed.push(day);
+                        return;
+                    }
+                };
+                let _ = io::stdout().write_all(&run.stdout);
+                let _ = io::stderr().write_all(&run.stderr);
+
+                if let Some(timeout) = run.timed_out {
+                    println!("\nTimed out after {timeout:?}.");
+                    timed_out.push(day);
+                } else if run.results.is_empty() {
+                    println!("Not solved.");
+                }
+
+                panicked.extend(describe_panics(day, &run.results));
+            },
+        );
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        print_missing_input(&missing_input);
+        print_timed_out(&timed_out);
+        print_failed(&failed);
+        print_panicked(&panicked);
+        return None;
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    for day in days {
+        print_header(day);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        let run = match child_commands::run_solution(
+            day,
+            is_timed,
+            is_release,
+            settings,
+            dispatcher.as_deref(),
+            timeouts.for_day(day),
+        ) {
+            Ok(run) => run,
+            Err(e) => {
+                println!("Failed to run solution: {e:?}");
+                failed.push(day);
+                continue;
+            }
+        };
+
+        panicked.extend(describe_panics(day, &run.results));
+
+        if let Some(timeout) = run.timed_out {
+            println!("\nTimed out after {timeout:?}.");
+            timed_out.push(day);
+        } else if run.results.is_empty() {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(&run.results, day);
+            val.bench_settings = is_timed.then(|| settings.clone());
+
+            if is_memory {
+                match child_commands::profile_heap(day) {
+                    Ok(stats) => {
+                        println!("{}", format_heap_stats(&stats));
+                        val.memory = Some(stats);
+                    }
+                    Err(e) => eprintln!("Failed to profile heap usage: {e:?}"),
+                }
             }
-        });
+
+            timings.push(val);
+        }
+    }
+
+    print_missing_input(&missing_input);
+    print_timed_out(&timed_out);
+    print_failed(&failed);
+    print_panicked(&panicked);
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,11 +163,124 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+fn print_missing_input(days: &[Day]) {
+    if !days.is_empty() {
+        let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+        println!(
+            "\n{ANSI_BOLD}Missing input:{ANSI_RESET} {ANSI_ITALIC}Day {}{ANSI_RESET}",
+            days.join(", ")
+        );
+    }
+}
+
+fn print_timed_out(days: &[Day]) {
+    if !days.is_empty() {
+        let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+        println!(
+            "\n{ANSI_BOLD}Timed out:{ANSI_RESET} {ANSI_ITALIC}Day {}{ANSI_RESET}",
+            days.join(", ")
+        );
+    }
+}
+
+/// Days whose solution could not be run, or whose results could not be read.
+fn print_failed(days: &[Day]) {
+    if !days.is_empty() {
+        let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+        println!(
+            "\n{ANSI_BOLD}Failed to run:{ANSI_RESET} {ANSI_ITALIC}Day {}{ANSI_RESET}",
+            days.join(", ")
+        );
+    }
+}
+
+/// Describes every part of `day` that panicked, e.g. `Day 04 Part 1: panicked at src/bin/04.rs:12:5: ...`.
+fn describe_panics(day: Day, results: &[PartResult]) -> Vec<String> {
+    results
+        .iter()
+        .filter(|r| r.status == Status::Panicked)
+        .map(|r| {
+            let phase = match r.part {
+                PARSE_PART => "Parse".to_string(),
+                part => format!("Part {part}"),
+            };
+            format!(
+                "Day {day} {phase}: {}",
+                r.error.as_deref().unwrap_or("panicked")
+            )
+        })
+        .collect()
+}
+
+fn print_panicked(panics: &[String]) {
+    if !panics.is_empty() {
+        println!("\n{ANSI_BOLD}Panicked:{ANSI_RESET}");
+        for panic in panics {
+            println!("  {ANSI_ITALIC}{panic}{ANSI_RESET}");
+        }
+    }
+}
+
+/// Run `run` for every day on up to `jobs` threads.
+/// Outcomes are passed to `on_done` in the order of `days`, as soon as all previous days are done.
+fn run_parallel<T: Send>(
+    days: &[Day],
+    jobs: usize,
+    run: impl Fn(Day) -> T + Sync,
+    mut on_done: impl FnMut(Day, T),
+) {
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|s| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let (next, run) = (&next, &run);
+
+            s.spawn(move || {
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    if tx.send((*day, run(*day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut pending: BTreeMap<Day, T> = BTreeMap::new();
+        let mut remaining = days.iter();
+
+        for (day, outcome) in rx {
+            pending.insert(day, outcome);
+
+            while let Some(outcome) = remaining
+                .as_slice()
+                .first()
+                .and_then(|day| pending.remove(day))
+            {
+                on_done(*remaining.next().unwrap(), outcome);
+            }
+        }
+    });
+}
+
+fn format_heap_stats(stats: &HeapStats) -> String {
+    format!(
+        "Heap: {} total, {} peak, {} allocations",
+        format_bytes(stats.total_bytes),
+        format_bytes(stats.peak_bytes),
+        stats.allocations
+    )
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Results(String),
+    Profile(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -65,193 +295,607 @@ pub fn get_path_for_bin(day: Day) -> String {
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their results.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        bench_settings::BenchSettings,
+        heap_stats::{HeapStats, DHAT_DIR_ENV},
+        results::{self, PartResult, Status, PARSE_PART, RESULTS_FILE_ENV},
+        Day,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+        collections::HashMap,
+        env, fs,
+        io::{self, Read},
+        path::{Path, PathBuf},
+        process::{self, Child, Command, ExitStatus, Stdio},
+        str::FromStr,
+        sync::{Arc, Mutex},
+        thread::{self, JoinHandle},
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    const DISPATCHER_BIN: &str = "dispatcher";
+
+    /// Build the dispatcher binary with a single cargo invocation and return its path.
+    /// Returns `None` if the build fails, e.g. because one of the days does not compile.
+    pub fn build_dispatcher(is_release: bool) -> Option<PathBuf> {
+        let executable = build_bin(DISPATCHER_BIN, is_release);
+
+        if executable.is_none() {
+            eprintln!(
+                "Could not build the dispatcher, falling back to building every day on its own."
+            );
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        executable
+    }
+
+    /// Build a binary of this crate and return the path of its executable.
+    /// Compiler diagnostics are forwarded to stderr.
+    fn build_bin(name: &str, is_release: bool) -> Option<PathBuf> {
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--bin",
+            name,
+            "--message-format=json-render-diagnostics",
+        ];
 
         if is_release {
             args.push("--release");
         }
 
+        Command::new("cargo")
+            .args(&args)
+            .stderr(Stdio::inherit())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .find_map(|line| parse_executable(line, name))
+            })
+    }
+
+    /// Extract the path of the executable built for `name` from a cargo JSON message.
+    fn parse_executable(line: &str, name: &str) -> Option<PathBuf> {
+        let json = JsonValue::from_str(line).ok()?;
+        let message = json.get::<HashMap<String, JsonValue>>()?;
+
+        let target_name = message
+            .get("target")?
+            .get::<HashMap<String, JsonValue>>()?
+            .get("name")?
+            .get::<String>()?;
+
+        if target_name != name {
+            return None;
+        }
+
+        message
+            .get("executable")?
+            .get::<String>()
+            .map(PathBuf::from)
+    }
+
+    /// Outcome of running the solution for a day in its own process.
+    #[derive(Debug, Default)]
+    pub struct SolutionRun {
+        pub results: Vec<PartResult>,
+        /// Output of the process, only collected if it ran with buffered stdio.
+        pub stdout: Vec<u8>,
+        pub stderr: Vec<u8>,
+        /// Set if the process was killed after exceeding its time limit.
+        pub timed_out: Option<Duration>,
+    }
+
+    /// Run the solution for a given day and collect the results it reports.
+    /// Days run in their own process, either through the dispatcher or through their own bin.
+    /// Output is forwarded as-is.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        settings: &BenchSettings,
+        dispatcher: Option<&Path>,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionRun, Error> {
+        run_command(
+            day, is_timed, is_release, settings, dispatcher, timeout, false,
+        )
+    }
+
+    /// Run the untimed solution for a given day, buffering its output instead of forwarding it.
+    pub fn run_solution_buffered(
+        day: Day,
+        is_release: bool,
+        settings: &BenchSettings,
+        dispatcher: Option<&Path>,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionRun, Error> {
+        run_command(day, false, is_release, settings, dispatcher, timeout, true)
+    }
+
+    fn run_command(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        settings: &BenchSettings,
+        dispatcher: Option<&Path>,
+        timeout: Option<Duration>,
+        is_buffered: bool,
+    ) -> Result<SolutionRun, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(SolutionRun::default());
+        }
+
+        let day_padded = day.to_string();
+
+        // the built executable is run directly rather than through `cargo run`,
+        // so that a timeout kills the solution and not just cargo.
+        let mut cmd = if let Some(dispatcher) = dispatcher {
+            let mut cmd = Command::new(dispatcher);
+            cmd.arg(&day_padded);
+            cmd
+        } else {
+            let Some(executable) = build_bin(&day_padded, is_release) else {
+                // the day does not compile, the diagnostics have been printed already.
+                return Ok(SolutionRun::default());
+            };
+            Command::new(executable)
+        };
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            cmd.arg("--time");
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // solutions report their results to a dedicated file.
+        let results_path = get_results_path(day);
+        let _ = fs::remove_file(&results_path);
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+        let stdio = || {
+            if is_buffered {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            }
+        };
+
+        let mut child = cmd
+            .env(RESULTS_FILE_ENV, &results_path)
+            .envs(settings.to_env())
+            .stdout(stdio())
+            .stderr(stdio())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let stdout = OutputCollector::new(child.stdout.take());
+        let stderr = OutputCollector::new(child.stderr.take());
+
+        let status = wait_with_timeout(&mut child, timeout)?;
+        let is_killed = status.is_none();
 
-        let mut output = vec![];
+        let results = results::read_file(&results_path).map_err(Error::Results);
+        let _ = fs::remove_file(&results_path);
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        Ok(SolutionRun {
+            results: results?,
+            stdout: stdout.finish(is_killed),
+            stderr: stderr.finish(is_killed),
+            timed_out: if is_killed { timeout } else { None },
+        })
+    }
+
+    /// Wait for `child` to exit. If it runs longer than `timeout`, it is killed and `None` is returned.
+    fn wait_with_timeout(
+        child: &mut Child,
+        timeout: Option<Duration>,
+    ) -> Result<Option<ExitStatus>, io::Error> {
+        let Some(timeout) = timeout else {
+            return child.wait().map(Some);
+        };
+
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(Some(status));
+            }
+
+            if Instant::now() >= deadline {
+                // the child may have exited in the meantime, in which case there is nothing to kill.
+                let _ = child.kill();
+                child.wait()?;
+                return Ok(None);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Reads a pipe of a child process on a separate thread, so that a full pipe does not block it.
+    struct OutputCollector {
+        buffer: Arc<Mutex<Vec<u8>>>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl OutputCollector {
+        fn new(pipe: Option<impl Read + Send + 'static>) -> Self {
+            let buffer = Arc::new(Mutex::new(vec![]));
+
+            let handle = pipe.map(|mut pipe| {
+                let buffer = Arc::clone(&buffer);
+                thread::spawn(move || {
+                    let mut chunk = [0; 8192];
+                    while let Ok(n @ 1..) = pipe.read(&mut chunk) {
+                        buffer.lock().unwrap().extend_from_slice(&chunk[..n]);
+                    }
+                })
             });
-        });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            Self { buffer, handle }
+        }
+
+        /// Output read so far. Unless the child was killed, this waits for the pipe to be closed.
+        /// A killed child may leave processes behind that keep the pipe open.
+        fn finish(self, is_killed: bool) -> Vec<u8> {
+            if let Some(handle) = self.handle.filter(|_| !is_killed) {
+                let _ = handle.join();
+            }
+
+            let mut buffer = self.buffer.lock().unwrap();
+            std::mem::take(&mut *buffer)
         }
+    }
+
+    /// Run the solution for a given day once more under the dhat heap profiler.
+    /// Every phase writes its own report, these are summed up into the stats of the whole day.
+    pub fn profile_heap(day: Day) -> Result<HeapStats, Error> {
+        let day_padded = day.to_string();
+
+        let dhat_dir = env::temp_dir().join(format!("aoc-dhat-{}-{day}", process::id()));
+        let _ = fs::remove_dir_all(&dhat_dir);
+        fs::create_dir_all(&dhat_dir)?;
+
+        let output = Command::new("cargo")
+            .args([
+                "run",
+                "--quiet",
+                "--profile",
+                "dhat",
+                "--features",
+                "dhat-heap",
+                "--bin",
+                &day_padded,
+            ])
+            .env(DHAT_DIR_ENV, &dhat_dir)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()?;
+
+        let stats = if output.status.success() {
+            read_heap_stats(&dhat_dir)
+        } else {
+            Err(Error::Profile(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ))
+        };
+
+        let _ = fs::remove_dir_all(&dhat_dir);
+        stats
+    }
+
+    fn read_heap_stats(dhat_dir: &Path) -> Result<HeapStats, Error> {
+        let mut paths = fs::read_dir(dhat_dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        paths.sort();
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        paths
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .try_fold(HeapStats::default(), |acc, path| {
+                let stats = HeapStats::from_dhat_json(&fs::read_to_string(path)?)
+                    .map_err(Error::Profile)?;
+                Ok(acc.combine(&stats))
+            })
+    }
 
-        Ok(output)
+    fn get_results_path(day: Day) -> PathBuf {
+        env::temp_dir().join(format!("aoc-results-{}-{day}.jsonl", process::id()))
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn parse_exec_time(results: &[PartResult], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            bench_settings: None,
+            memory: None,
             total_nanos: 0_f64,
         };
 
-        output
+        // only benched parts produce timings.
+        results
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|r| r.status == Status::Solved && r.summary.samples > 1)
+            .for_each(|r| {
+                let timing_str = format!("{:.1?}", r.summary.mean);
+
+                match r.part {
+                    PARSE_PART => {
+                        timings.parse = Some(timing_str);
+                        timings.parse_stats = Some(r.summary.clone());
+                    }
+                    1 => {
+                        timings.part_1 = Some(timing_str);
+                        timings.part_1_stats = Some(r.summary.clone());
+                    }
+                    2 => {
+                        timings.part_2 = Some(timing_str);
+                        timings.part_2_stats = Some(r.summary.clone());
+                    }
+                    _ => return,
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+                timings.total_nanos += r.summary.mean.as_nanos() as f64;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{
+            parse_exec_time, parse_executable, read_heap_stats, wait_with_timeout, OutputCollector,
+        };
+        use std::path::PathBuf;
+        use std::process::{Command, Stdio};
+        use std::time::Duration;
+        use std::time::Instant;
+        use std::{env, fs, process};
+
+        use crate::{
+            day,
+            template::{
+                heap_stats::HeapStats,
+                results::{PartResult, Status, PARSE_PART},
+                stats::Summary,
+            },
+        };
 
-        use crate::day;
+        fn result(part: u8, answer: Option<&str>, samples: &[u64]) -> PartResult {
+            let samples: Vec<Duration> = samples.iter().map(|x| Duration::from_nanos(*x)).collect();
+            PartResult {
+                part,
+                answer: answer.map(Into::into),
+                status: if answer.is_some() {
+                    Status::Solved
+                } else {
+                    Status::Unsolved
+                },
+                error: None,
+                summary: Summary::from_samples(&samples),
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    result(1, Some("0"), &[70, 78]),
+                    result(2, Some("10"), &[74_000_000, 74_260_000]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total_nanos, 74_130_074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(res.part_1_stats.unwrap().min, Duration::from_nanos(70));
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
+        fn ignores_output_printed_by_solutions() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    result(1, Some("Part 2: 10 (2s @ 5 samples)"), &[2_000, 2_000]),
+                    result(2, Some("10s"), &[100, 100]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total_nanos, 2100_f64);
+            assert_eq!(res.part_1.unwrap(), "2.0µs");
+            assert_eq!(res.part_2.unwrap(), "100.0ns");
         }
 
         #[test]
         fn parses_missing_parts() {
+            let res = parse_exec_time(
+                &[result(1, None, &[10, 10]), result(2, None, &[10, 10])],
+                day!(1),
+            );
+            assert_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_dispatcher_executable() {
+            let line = r#"{"reason":"compiler-artifact","target":{"kind":["bin"],"name":"dispatcher"},"executable":"/repo/target/release/dispatcher"}"#;
+            assert_eq!(
+                parse_executable(line, "dispatcher"),
+                Some(PathBuf::from("/repo/target/release/dispatcher"))
+            );
+            assert_eq!(parse_executable(line, "01"), None);
+        }
+
+        #[test]
+        fn ignores_other_artifacts() {
+            let lib = r#"{"reason":"compiler-artifact","target":{"kind":["lib"],"name":"advent_of_code"},"executable":null}"#;
+            assert_eq!(parse_executable(lib, "dispatcher"), None);
+            assert_eq!(
+                parse_executable(r#"{"reason":"build-finished","success":true}"#, "01"),
+                None
+            );
+        }
+
+        #[test]
+        fn parses_parse_phase() {
             let res = parse_exec_time(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    PartResult {
+                        status: Status::Solved,
+                        ..result(PARSE_PART, None, &[1_000, 1_000])
+                    },
+                    result(1, Some("1"), &[100, 100]),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert_eq!(res.total_nanos, 1100_f64);
+            assert_eq!(res.parse.unwrap(), "1.0µs");
+            assert_eq!(res.part_1.unwrap(), "100.0ns");
+        }
+
+        #[test]
+        fn sums_heap_stats_of_all_phases() {
+            let dir = env::temp_dir().join(format!("aoc-dhat-test-{}", process::id()));
+            fs::create_dir_all(&dir).unwrap();
+
+            let report = |tb: u64, gb: u64, tbk: u64| {
+                format!(r#"{{ "pps": [{{ "tb": {tb}, "tbk": {tbk}, "gb": {gb}, "gbk": 1 }}] }}"#)
+            };
+            fs::write(dir.join("part-0.json"), report(100, 80, 2)).unwrap();
+            fs::write(dir.join("part-1.json"), report(50, 20, 3)).unwrap();
+            fs::write(dir.join("notes.txt"), "not a report").unwrap();
+
+            let stats = read_heap_stats(&dir);
+            fs::remove_dir_all(&dir).unwrap();
+
+            assert_eq!(
+                stats.unwrap(),
+                HeapStats {
+                    total_bytes: 150,
+                    peak_bytes: 80,
+                    allocations: 5,
+                }
+            );
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn kills_children_after_timeout() {
+            let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+            let timer = Instant::now();
+            let status = wait_with_timeout(&mut child, Some(Duration::from_millis(50))).unwrap();
+            assert_eq!(status.is_none(), true);
+            assert_eq!(timer.elapsed() < Duration::from_secs(5), true);
+        }
+
+        #[test]
+        #[cfg(unix)]
+        fn waits_for_children_within_timeout() {
+            let mut child = Command::new("sh")
+                .args(["-c", "echo done"])
+                .stdout(Stdio::piped())
+                .spawn()
+                .unwrap();
+            let stdout = OutputCollector::new(child.stdout.take());
+            let status = wait_with_timeout(&mut child, Some(Duration::from_secs(10))).unwrap();
+            assert_eq!(status.unwrap().success(), true);
+            assert_eq!(stdout.finish(false), b"done\n");
+        }
+
+        #[test]
+        fn skips_untimed_parts() {
+            let res = parse_exec_time(&[result(1, Some("42"), &[10])], day!(1));
+            assert_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
         }
     }
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{describe_panics, run_parallel, PartResult, Status, PARSE_PART};
+    use crate::{day, template::stats::Summary};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn describes_panicked_parts() {
+        let result = |part, status, error: Option<&str>| PartResult {
+            part,
+            answer: None,
+            status,
+            error: error.map(Into::into),
+            summary: Summary::from_samples(&[Duration::from_nanos(1)]),
+        };
+
+        let results = [
+            result(
+                1,
+                Status::Panicked,
+                Some("panicked at src/bin/04.rs:1:1: oops"),
+            ),
+            result(2, Status::Unsolved, None),
+        ];
+
+        assert_eq!(
+            describe_panics(day!(4), &results),
+            vec!["Day 04 Part 1: panicked at src/bin/04.rs:1:1: oops"]
+        );
+
+        let results = [result(
+            PARSE_PART,
+            Status::Panicked,
+            Some("panicked at src/bin/05.rs:1:1: bad input"),
+        )];
+
+        assert_eq!(
+            describe_panics(day!(5), &results),
+            vec!["Day 05 Parse: panicked at src/bin/05.rs:1:1: bad input"]
+        );
+    }
+
+    #[test]
+    fn runs_days_in_parallel_in_order() {
+        let days = [day!(1), day!(2), day!(3), day!(4), day!(5)];
+        let mut done = vec![];
+
+        // earlier days take longer, so they finish last.
+        run_parallel(
+            &days,
+            3,
+            |day| {
+                thread::sleep(Duration::from_millis(10 * u64::from(6 - day.into_inner())));
+                day.into_inner() * 10
+            },
+            |day, outcome| done.push((day.into_inner(), outcome)),
+        );
+
+        assert_eq!(done, vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50)]);
+    }
+
+    #[test]
+    fn runs_days_with_more_jobs_than_days() {
+        let mut done = vec![];
+        run_parallel(
+            &[day!(7)],
+            8,
+            |day| day,
+            |day, outcome| {
+                done.push((day, outcome));
+            },
+        );
+        assert_eq!(done, vec![(day!(7), day!(7))]);
+    }
+}
@@ -2,38 +2,362 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::bench_settings::BenchSettings;
+#[cfg(feature = "dhat-heap")]
+use crate::template::heap_stats::DHAT_DIR_ENV;
+use crate::template::panics::{self, PanicReport};
+use crate::template::results::{self, PartResult, Status, PARSE_PART};
+use crate::template::stats::Summary;
+use crate::template::submissions::{self, Submission, Verdict};
+use crate::template::{
+    answers,
+    aoc_client::{Client, ClientError},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Return types of solution parts: `Option<T>` for parts that might not be solved yet, or
+/// `Result<T, E>` for parts that can fail, e.g. on malformed input.
+pub trait IntoAnswer {
+    type Answer: Display;
+
+    /// `Ok(None)` if the part is not solved, `Err` with the error text if it failed.
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Display> IntoAnswer for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+/// Return types of parse functions passed to `solution!`: `Option<T>` or `Result<T, E>`, so that
+/// malformed input is reported as an error instead of a panic.
+pub trait IntoParsed {
+    type Parsed;
+
+    /// `Err` with the error text if the input could not be parsed.
+    fn into_parsed(self) -> Result<Self::Parsed, String>;
+}
+
+impl<T> IntoParsed for Option<T> {
+    type Parsed = T;
+
+    fn into_parsed(self) -> Result<T, String> {
+        self.ok_or_else(|| "could not parse the input".into())
+    }
+}
+
+impl<T, E: Display> IntoParsed for Result<T, E> {
+    type Parsed = T;
+
+    fn into_parsed(self) -> Result<T, String> {
+        self.map_err(|e| e.to_string())
+    }
+}
+
+/// Name of the implementation that is passed to `solution!` as `part_one` or `part_two`.
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+/// A named implementation of a part, with its answer converted by [`IntoAnswer`], so that
+/// implementations returning `Option` and `Result` can be mixed.
+pub type Implementation<I, T> = (&'static str, fn(I) -> Result<Option<T>, String>);
+
+/// Run the implementations of a part that were requested on the command line:
+///  1. with `--compare`, all of them are checked for agreement and benched side by side.
+///  2. with `--impl <name>`, only the named one is run.
+///  3. otherwise, the default one is run.
+pub fn run_implementations<I: Copy, T: Display>(
+    implementations: &[Implementation<I, T>],
+    input: I,
+    day: Day,
+    part: u8,
+) {
+    let args: Vec<String> = env::args().collect();
+
+    if args.contains(&"--compare".into()) {
+        compare_implementations(implementations, input, part);
+        return;
+    }
+
+    let name = args
+        .iter()
+        .position(|x| x == "--impl")
+        .and_then(|i| args.get(i + 1))
+        .map_or(DEFAULT_IMPLEMENTATION, String::as_str);
+
+    match implementations.iter().find(|(n, _)| *n == name) {
+        Some((_, func)) => run_answer(func, input, day, part),
+        None => {
+            let names: Vec<&str> = implementations.iter().map(|(n, _)| *n).collect();
+            eprintln!(
+                "Part {part}: no implementation named `{name}`, available: {}.",
+                names.join(", ")
+            );
+            process::exit(1);
+        }
+    }
+}
+
+pub fn run_part<I: Copy, R: IntoAnswer>(func: impl Fn(I) -> R, input: I, day: Day, part: u8) {
+    run_answer(|input| func(input).into_answer(), input, day, part);
+}
+
+fn run_answer<I: Copy, T: Display>(
+    func: impl Fn(I) -> Result<Option<T>, String>,
+    input: I,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let timer = Instant::now();
+    let run = panics::catch(|| {
+        run_timed(func, input, part, |result| {
+            print_result(result, &part_str, "");
+        })
+    });
+
+    let (result, summary) = match run {
+        Ok(run) => run,
+        Err(report) => {
+            print_panic(&report, &part_str);
+
+            let record = PartResult {
+                part,
+                answer: None,
+                status: Status::Panicked,
+                error: Some(report.to_string()),
+                summary: Summary::from_samples(&[timer.elapsed()]),
+            };
+
+            if let Err(e) = results::emit(&record) {
+                eprintln!("Failed to report result of {part_str}: {e}");
+            }
+            return;
+        }
+    };
+
+    print_result(&result, &part_str, &format_duration(&summary));
+
+    if summary.samples > 1 {
+        println!("{}", format_summary(&summary, &part_str));
+    }
+
+    let record = PartResult {
+        part,
+        answer: result
+            .as_ref()
+            .ok()
+            .and_then(Option::as_ref)
+            .map(ToString::to_string),
+        status: match &result {
+            Ok(Some(_)) => Status::Solved,
+            Ok(None) => Status::Unsolved,
+            Err(_) => Status::Failed,
+        },
+        error: result.as_ref().err().cloned(),
+        summary,
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if let Err(e) = results::emit(&record) {
+        eprintln!("Failed to report result of {part_str}: {e}");
+    }
 
-    if let Some(result) = result {
+    if let Ok(Some(result)) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run the parse phase shared by both parts and report its timing as its own row.
+/// Returns `None` if parsing failed or panicked, so that both parts can be skipped.
+pub fn run_parse<I: Copy, R: IntoParsed>(func: impl Fn(I) -> R, input: I) -> Option<R::Parsed> {
+    let label = "Parse";
+
+    let timer = Instant::now();
+    let run = panics::catch(|| {
+        run_timed(
+            |input| func(input).into_parsed(),
+            input,
+            PARSE_PART,
+            |parsed| print!("{label}: {}", if parsed.is_ok() { "✔" } else { "✖" }),
+        )
+    });
+
+    let (parsed, summary) = match run {
+        Ok(run) => run,
+        Err(report) => {
+            print_panic(&report, label);
+
+            let record = PartResult {
+                part: PARSE_PART,
+                answer: None,
+                status: Status::Panicked,
+                error: Some(report.to_string()),
+                summary: Summary::from_samples(&[timer.elapsed()]),
+            };
+
+            if let Err(e) = results::emit(&record) {
+                eprintln!("Failed to report result of {label}: {e}");
+            }
+            return None;
+        }
+    };
+
+    print!("\r");
+    match &parsed {
+        Ok(_) => println!("{label}: ✔{}", format_duration(&summary)),
+        Err(e) => println!("{label}: ✖ {ANSI_ITALIC}error: {e}{ANSI_RESET}"),
+    }
+
+    if parsed.is_ok() && summary.samples > 1 {
+        println!("{}", format_summary(&summary, label));
+    }
+
+    let record = PartResult {
+        part: PARSE_PART,
+        answer: None,
+        status: if parsed.is_ok() {
+            Status::Solved
+        } else {
+            Status::Failed
+        },
+        error: parsed.as_ref().err().cloned(),
+        summary,
+    };
+
+    if let Err(e) = results::emit(&record) {
+        eprintln!("Failed to report result of {label}: {e}");
+    }
+
+    parsed.ok()
+}
+
+/// Report a part that was not run because its input could not be parsed.
+pub fn skip_part(part: u8) {
+    let error = "skipped, the input could not be parsed";
+    println!("Part {part}: ✖ {ANSI_ITALIC}{error}{ANSI_RESET}");
+
+    let record = PartResult {
+        part,
+        answer: None,
+        status: Status::Failed,
+        error: Some(error.into()),
+        summary: Summary::from_samples(&[Duration::ZERO]),
+    };
+
+    if let Err(e) = results::emit(&record) {
+        eprintln!("Failed to report result of Part {part}: {e}");
+    }
+}
+
+/// Check that all implementations of a part agree on the answer, then bench them against each other.
+fn compare_implementations<I: Copy, T: Display>(
+    implementations: &[Implementation<I, T>],
+    input: I,
+    part: u8,
+) {
+    let mut answers = vec![];
+    let mut base_times = vec![];
+
+    for (_, func) in implementations {
+        let timer = Instant::now();
+        let answer = panics::catch(|| func(input))
+            .unwrap_or_else(|report| Err(report.to_string()))
+            .map(|answer| answer.map(|answer| answer.to_string()));
+        base_times.push(timer.elapsed());
+        answers.push(answer);
+    }
+
+    let format_answer = |answer: &Result<Option<String>, String>| match answer {
+        Ok(Some(answer)) => answer.clone(),
+        Ok(None) => "✖".into(),
+        Err(e) => format!("✖ error: {e}"),
+    };
+    let name_width = implementations
+        .iter()
+        .map(|(name, _)| name.len())
+        .chain(["Implementation".len()])
+        .max()
+        .unwrap_or(0);
+    let answer_width = answers
+        .iter()
+        .map(|answer| format_answer(answer).chars().count())
+        .chain(["Answer".len()])
+        .max()
+        .unwrap_or(0);
+
+    println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+    if answers
+        .iter()
+        .any(|answer| answer.is_err() || *answer != answers[0])
+    {
+        for ((name, _), answer) in implementations.iter().zip(&answers) {
+            println!("  {name:<name_width$}  {}", format_answer(answer));
+        }
+        eprintln!("Implementations of part {part} failed or disagree, not benching.");
+        process::exit(1);
+    }
+
+    println!(
+        "  {:<name_width$}  {:<answer_width$}  {:>10}  {:>10}  {:>8}",
+        "Implementation", "Answer", "Mean", "Median", "Samples"
+    );
+
+    let mut means = vec![];
+
+    for (((name, func), answer), base_time) in implementations.iter().zip(&answers).zip(&base_times)
+    {
+        print!("  {name:<name_width$}");
+        let summary = bench(func, input, base_time);
+        print!("\r");
+        println!(
+            "  {name:<name_width$}  {:<answer_width$}  {:>10}  {:>10}  {:>8}",
+            format_answer(answer),
+            format!("{:.1?}", summary.mean),
+            format!("{:.1?}", summary.median),
+            summary.samples,
+        );
+        means.push((*name, summary.mean));
+    }
+
+    let (fastest, fastest_mean) = *means.iter().min_by_key(|(_, mean)| *mean).unwrap();
+    if let Some((slowest, slowest_mean)) = means.iter().max_by_key(|(_, mean)| *mean) {
+        if *slowest != fastest {
+            println!(
+                "  {fastest} is {:.1}x faster than {slowest}.",
+                slowest_mean.as_secs_f64() / fastest_mean.as_secs_f64().max(f64::EPSILON)
+            );
+        }
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. with `--time`, the function is benched according to [`BenchSettings`] (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    #[cfg_attr(not(feature = "dhat-heap"), allow(unused_variables))] part: u8,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Summary) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+        let _profiler = heap_profiler(part);
 
         func(input)
     };
@@ -41,23 +365,42 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let summary = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Summary::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (result, summary)
+}
+
+/// Profile the heap usage of one phase. If `AOC_DHAT_DIR` is set, the report is written to `<dir>/part-<n>.json` instead of `dhat-heap.json`, so that `time --memory` can collect every phase.
+#[cfg(feature = "dhat-heap")]
+fn heap_profiler(part: u8) -> dhat::Profiler {
+    match env::var(DHAT_DIR_ENV) {
+        Ok(dir) => dhat::Profiler::builder()
+            .file_name(std::path::Path::new(&dir).join(format!("part-{part}.json")))
+            .build(),
+        Err(_) => dhat::Profiler::new_heap(),
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Summary {
+    let settings = BenchSettings::from_env().unwrap_or_else(|e| {
+        eprintln!("\nInvalid bench settings: {e}");
+        process::exit(1);
+    });
+
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    for _ in 0..settings.warmup {
+        black_box(func(black_box(input)));
+    }
+
+    let bench_iterations = settings.iterations(*base_time);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,22 +410,12 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Summary::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_duration(summary: &Summary) -> String {
+    let duration = summary.mean;
+    let samples = summary.samples;
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -90,11 +423,19 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Formats the distribution of a benched part.
+fn format_summary(summary: &Summary, part: &str) -> String {
+    format!(
+        "{part} stats: min {:.1?}, median {:.1?}, p95 {:.1?}, max {:.1?}, std dev {:.1?}, {} outliers",
+        summary.min, summary.median, summary.p95, summary.max, summary.std_dev, summary.outliers
+    )
+}
+
+fn print_result<T: Display>(result: &Result<Option<T>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(Some(result)) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
@@ -114,7 +455,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -122,17 +463,33 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(e) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!("{part}: ✖ {ANSI_ITALIC}error: {e}{ANSI_RESET}{duration_str}");
+            }
+        }
+    }
+}
+
+fn print_panic(report: &PanicReport, part: &str) {
+    print!("\r");
+    println!("{part}: ✖ {ANSI_ITALIC}{report}{ANSI_RESET}");
+
+    match report.backtrace() {
+        Some(backtrace) => println!("{backtrace}"),
+        None => println!(
+            "{ANSI_ITALIC}note: run with `RUST_BACKTRACE=1` to display a backtrace{ANSI_RESET}"
+        ),
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is set up.
+fn submit_result<T: Display>(result: T, day: Day, part: u8) -> Option<Result<String, ClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +512,68 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let client = Client::from_env().unwrap_or_else(|e| {
+        eprintln!("Not submitting, {e}");
         process::exit(1);
+    });
+
+    let answer = result.to_string();
+
+    let submissions = submissions::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read previous submissions: {e}");
+        process::exit(1);
+    });
+
+    if let Some(wait) = submissions::cooldown(&submissions, submissions::now()) {
+        eprintln!(
+            "Not submitting, Advent of Code asked to wait before the next answer. Try again in {}.",
+            submissions::format_countdown(wait)
+        );
+        process::exit(1);
+    }
+
+    if let Err(reason) = submissions::check_answer(&submissions, day, part, &answer) {
+        eprintln!("Not submitting, {reason}");
+        process::exit(1);
+    }
+
+    println!("Submitting result...");
+    let response = client.submit(day, part, &answer);
+
+    match &response {
+        Ok(response) => {
+            println!("{response}");
+            record_verdict(day, part, &answer, response);
+        }
+        Err(e) => eprintln!("Failed to submit: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(response)
+}
+
+/// Classify the response to a submission and remember it for later submissions.
+fn record_verdict(day: Day, part: u8, answer: &str, response: &str) {
+    let Some(submission) = Submission::new(day, part, answer, response) else {
+        eprintln!("Could not determine the verdict of the submission.");
+        return;
+    };
+
+    println!("Verdict: {ANSI_BOLD}{}{ANSI_RESET}", submission.verdict);
+
+    if let Some(wait) = submission.wait {
+        println!(
+            "Next submission possible in {}.",
+            submissions::format_countdown(wait)
+        );
+    }
+
+    if let Err(e) = submissions::append(&submission) {
+        eprintln!("Failed to record submission: {e}");
+    }
+
+    if submission.verdict == Verdict::Correct {
+        if let Err(e) = answers::write(day, part, answer) {
+            eprintln!("Failed to store accepted answer: {e}");
+        }
+    }
 }
@@ -0,0 +1,115 @@
+/// Summary statistics for benchmark samples.
+use std::time::Duration;
+
+/// Distribution of a set of benchmark samples.
+///
+/// Samples outside of the Tukey fences (`1.5 * IQR` beyond the first and third quartile) are
+/// treated as outliers and excluded from all other values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub samples: u128,
+    pub outliers: u128,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+/// Outlier rejection needs a few samples to produce meaningful quartiles.
+const MIN_SAMPLES_FOR_REJECTION: usize = 4;
+
+impl Summary {
+    /// Computes a summary for the given samples. Panics if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "cannot summarize empty samples.");
+
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let kept: Vec<f64> = if sorted.len() >= MIN_SAMPLES_FOR_REJECTION {
+            let q1 = percentile(&sorted, 25.0);
+            let q3 = percentile(&sorted, 75.0);
+            let fence = 1.5 * (q3 - q1);
+            sorted
+                .iter()
+                .copied()
+                .filter(|x| *x >= q1 - fence && *x <= q3 + fence)
+                .collect()
+        } else {
+            sorted.clone()
+        };
+
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let variance = kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        Summary {
+            samples: sorted.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+            mean: from_nanos(mean),
+            std_dev: from_nanos(variance.sqrt()),
+            min: from_nanos(kept[0]),
+            median: from_nanos(percentile(&kept, 50.0)),
+            p95: from_nanos(percentile(&kept, 95.0)),
+            max: from_nanos(kept[kept.len() - 1]),
+        }
+    }
+}
+
+/// Linear interpolation between closest ranks. `sorted` must be sorted and non-empty.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Summary;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn summarizes_single_sample() {
+        let summary = Summary::from_samples(&nanos(&[100]));
+        assert_eq!(summary.samples, 1);
+        assert_eq!(summary.outliers, 0);
+        assert_eq!(summary.mean, Duration::from_nanos(100));
+        assert_eq!(summary.min, Duration::from_nanos(100));
+        assert_eq!(summary.max, Duration::from_nanos(100));
+        assert_eq!(summary.std_dev, Duration::ZERO);
+    }
+
+    #[test]
+    fn computes_distribution() {
+        let summary = Summary::from_samples(&nanos(&[40, 10, 30, 20, 50]));
+        assert_eq!(summary.samples, 5);
+        assert_eq!(summary.outliers, 0);
+        assert_eq!(summary.mean, Duration::from_nanos(30));
+        assert_eq!(summary.min, Duration::from_nanos(10));
+        assert_eq!(summary.median, Duration::from_nanos(30));
+        assert_eq!(summary.p95, Duration::from_nanos(48));
+        assert_eq!(summary.max, Duration::from_nanos(50));
+        assert_eq!(summary.std_dev, Duration::from_nanos(14));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let summary = Summary::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 5000]));
+        assert_eq!(summary.samples, 7);
+        assert_eq!(summary.outliers, 1);
+        assert_eq!(summary.max, Duration::from_nanos(12));
+        assert_eq!(summary.mean, Duration::from_nanos(11));
+    }
+}
@@ -0,0 +1,520 @@
+/// Log of answers submitted through `solve --submit`, together with the verdict of Advent of Code.
+///
+/// The log is used to refuse submissions locally that are known to be wrong, either because the
+/// same answer was already rejected or because it is out of the bounds of a "too high" or
+/// "too low" verdict. It also remembers when Advent of Code asked to wait before submitting again.
+/// Every account has a log of its own, e.g. `data/submissions/alice.jsonl`.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{accounts, Day};
+
+static SUBMISSIONS_FILE_PATH: &str = "./data/submissions.jsonl";
+
+fn get_path() -> PathBuf {
+    match accounts::active_name() {
+        Some(name) => PathBuf::from(format!("./data/submissions/{name}.jsonl")),
+        None => PathBuf::from(SUBMISSIONS_FILE_PATH),
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited,
+}
+
+impl Verdict {
+    /// Classifies the response of Advent of Code to a submitted answer.
+    pub fn parse(response: &str) -> Option<Self> {
+        let response = normalize(response);
+
+        if response.contains("you gave an answer too recently") {
+            Some(Verdict::RateLimited)
+        } else if response.contains("that's the right answer") {
+            Some(Verdict::Correct)
+        } else if response.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if response.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if response.contains("that's not the right answer") {
+            Some(Verdict::Incorrect)
+        } else {
+            None
+        }
+    }
+
+    /// Whether this verdict rejects the submitted answer.
+    pub fn is_rejection(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::RateLimited => "rate limited",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "rate limited" => Ok(Verdict::RateLimited),
+            x => Err(format!("Unknown submission verdict `{x}`.")),
+        }
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Parses how long Advent of Code asks to wait before the next submission, if at all.
+pub fn parse_wait(response: &str) -> Option<Duration> {
+    let response = normalize(response);
+
+    // e.g. "you have 1m 5s left to wait."
+    if let Some((before, _)) = response.split_once(" left to wait") {
+        let (_, wait) = before.rsplit_once("you have ")?;
+        return wait
+            .split_whitespace()
+            .map(|token| {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()
+            .map(Duration::from_secs);
+    }
+
+    // e.g. "please wait one minute before trying again." or "please wait 5 minutes ..."
+    let (_, rest) = response.split_once("please wait ")?;
+    let mut words = rest.split_whitespace();
+    let value = match words.next()? {
+        "one" | "a" => 1,
+        x => x.parse().ok()?,
+    };
+
+    match words.next()? {
+        "second" | "seconds" => Some(Duration::from_secs(value)),
+        "minute" | "minutes" => Some(Duration::from_secs(value * 60)),
+        "hour" | "hours" => Some(Duration::from_secs(value * 3600)),
+        _ => None,
+    }
+}
+
+/// Lower-cases the response and collapses whitespace, since it may be wrapped over several lines.
+fn normalize(response: &str) -> String {
+    response
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// A single answer submitted to Advent of Code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// How long Advent of Code asked to wait before submitting again.
+    pub wait: Option<Duration>,
+}
+
+impl Submission {
+    /// Creates a submission that received `response` just now.
+    /// Returns `None` if the verdict could not be determined.
+    pub fn new(day: Day, part: u8, answer: &str, response: &str) -> Option<Self> {
+        Some(Self {
+            day,
+            part,
+            answer: answer.trim().to_string(),
+            verdict: Verdict::parse(response)?,
+            timestamp: now(),
+            wait: parse_wait(response),
+        })
+    }
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Time left until submitting is allowed again, if a previous submission is still cooling down.
+pub fn cooldown(submissions: &[Submission], now: u64) -> Option<Duration> {
+    submissions
+        .iter()
+        .filter_map(|s| Some(s.timestamp + s.wait?.as_secs()))
+        .max()
+        .filter(|until| *until > now)
+        .map(|until| Duration::from_secs(until - now))
+}
+
+/// Formats a wait time as a countdown, e.g. `4m 05s`.
+pub fn format_countdown(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 3600, (secs % 3600) / 60, secs % 60) {
+        (0, 0, s) => format!("{s}s"),
+        (0, m, s) => format!("{m}m {s:02}s"),
+        (h, m, s) => format!("{h}h {m:02}m {s:02}s"),
+    }
+}
+
+/// Checks `answer` against earlier submissions of the same part.
+/// Returns the reason if the answer is known to be wrong.
+pub fn check_answer(
+    submissions: &[Submission],
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> Result<(), String> {
+    let answer = answer.trim();
+    let previous = submissions
+        .iter()
+        .filter(|s| s.day == day && s.part == part);
+
+    if let Some(rejected) = previous
+        .clone()
+        .find(|s| s.answer == answer && s.verdict.is_rejection())
+    {
+        return Err(format!(
+            "{answer} was already rejected ({}).",
+            rejected.verdict
+        ));
+    }
+
+    // bounds only apply to numeric answers.
+    let Ok(value) = answer.parse::<i128>() else {
+        return Ok(());
+    };
+
+    let bound = |verdict: Verdict| {
+        previous
+            .clone()
+            .filter(move |s| s.verdict == verdict)
+            .filter_map(|s| s.answer.parse::<i128>().ok())
+    };
+
+    if let Some(too_high) = bound(Verdict::TooHigh).min().filter(|x| value >= *x) {
+        return Err(format!(
+            "{answer} is not lower than {too_high}, which was too high."
+        ));
+    }
+
+    if let Some(too_low) = bound(Verdict::TooLow).max().filter(|x| value <= *x) {
+        return Err(format!(
+            "{answer} is not higher than {too_low}, which was too low."
+        ));
+    }
+
+    Ok(())
+}
+
+/// Append a submission to the log.
+pub fn append(submission: &Submission) -> Result<(), io::Error> {
+    let line = JsonValue::from(submission)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let path = get_path();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all submissions from the log. If not present, returns an empty log.
+pub fn read_from_file() -> Result<Vec<Submission>, String> {
+    match fs::read_to_string(get_path()) {
+        Ok(content) => parse(&content),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse(content: &str) -> Result<Vec<Submission>, String> {
+    content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("submissions contain invalid JSON."))?;
+            Submission::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "wait_secs".into(),
+            value
+                .wait
+                .map_or(JsonValue::Null, |d| JsonValue::Number(d.as_secs() as f64)),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?
+            .clone();
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let wait = json
+            .get("wait_secs")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| Duration::from_secs(*x as u64));
+
+        Ok(Submission {
+            day,
+            part,
+            answer,
+            verdict,
+            timestamp,
+            wait,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_answer, cooldown, format_countdown, parse, parse_wait, Submission, Verdict};
+    use crate::day;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    const RATE_LIMITED_RESPONSE: &str = include_str!("fixtures/submit_rate_limited.txt");
+    const INCORRECT_RESPONSE: &str = include_str!("fixtures/submit_incorrect.txt");
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: 1_733_029_200,
+            wait: None,
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::parse("That's the right answer! You are one gold star closer to finding the Chief Historian."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too high. If you're stuck, make sure you're using the full input data."),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low. Please wait one minute before trying again."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer. If you're stuck, there are some general tips on the about page."),
+            Some(Verdict::Incorrect)
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 45s left to wait."),
+            Some(Verdict::RateLimited)
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = [submission(1, "42", Verdict::Incorrect)];
+        assert_eq!(check_answer(&submissions, day!(1), 1, "42").is_err(), true);
+        assert_eq!(check_answer(&submissions, day!(1), 1, "43").is_ok(), true);
+        assert_eq!(check_answer(&submissions, day!(1), 2, "42").is_ok(), true);
+        assert_eq!(check_answer(&submissions, day!(2), 1, "42").is_ok(), true);
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let submissions = [
+            submission(1, "100", Verdict::TooHigh),
+            submission(1, "150", Verdict::TooHigh),
+            submission(1, "10", Verdict::TooLow),
+        ];
+        assert_eq!(check_answer(&submissions, day!(1), 1, "120").is_err(), true);
+        assert_eq!(check_answer(&submissions, day!(1), 1, "100").is_err(), true);
+        assert_eq!(check_answer(&submissions, day!(1), 1, "10").is_err(), true);
+        assert_eq!(check_answer(&submissions, day!(1), 1, "-5").is_err(), true);
+        assert_eq!(check_answer(&submissions, day!(1), 1, "50").is_ok(), true);
+        assert_eq!(check_answer(&submissions, day!(1), 1, "abc").is_ok(), true);
+    }
+
+    #[test]
+    fn ignores_rate_limited_answers() {
+        let submissions = [submission(1, "42", Verdict::RateLimited)];
+        assert_eq!(check_answer(&submissions, day!(1), 1, "42").is_ok(), true);
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let entry = submission(2, "11387", Verdict::TooLow);
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert_eq!(
+            parse(&format!("{line}\n\n{line}")).unwrap(),
+            vec![entry.clone(), entry]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_submissions() {
+        parse(r#"{ "day": "01", "part": 1, "answer": "42", "verdict": "maybe", "timestamp": 0 }"#)
+            .unwrap();
+    }
+
+    #[test]
+    fn parses_recorded_responses() {
+        let submission = Submission::new(day!(7), 1, "3749", RATE_LIMITED_RESPONSE).unwrap();
+        assert_eq!(submission.verdict, Verdict::RateLimited);
+        assert_eq!(submission.wait, Some(Duration::from_secs(4 * 60 + 32)));
+
+        let submission = Submission::new(day!(7), 1, "3749", INCORRECT_RESPONSE).unwrap();
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+        assert_eq!(submission.wait, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("You have 45s left to wait."),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(
+            parse_wait("please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn computes_cooldowns() {
+        let submissions = [
+            Submission {
+                wait: Some(Duration::from_secs(60)),
+                ..submission(1, "42", Verdict::Incorrect)
+            },
+            submission(1, "43", Verdict::Correct),
+        ];
+        let submitted = submissions[0].timestamp;
+
+        assert_eq!(
+            cooldown(&submissions, submitted + 15),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(cooldown(&submissions, submitted + 60), None);
+        assert_eq!(cooldown(&[], submitted), None);
+    }
+
+    #[test]
+    fn formats_countdowns() {
+        assert_eq!(format_countdown(Duration::from_secs(9)), "9s");
+        assert_eq!(format_countdown(Duration::from_secs(272)), "4m 32s");
+        assert_eq!(format_countdown(Duration::from_secs(3605)), "1h 00m 05s");
+    }
+}
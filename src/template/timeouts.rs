@@ -0,0 +1,102 @@
+/// Time limits for running a day in `all` and `time`.
+///
+/// A global limit is read from `AOC_TIMEOUT_SECS` (or passed with `--timeout`), and can be
+/// overridden for a single day with e.g. `AOC_TIMEOUT_SECS_06`. Without a limit, days run until
+/// they finish.
+use std::{collections::HashMap, env, time::Duration};
+
+use crate::template::{all_days, Day};
+
+pub const TIMEOUT_ENV: &str = "AOC_TIMEOUT_SECS";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Limit for days that do not have their own.
+    pub default: Option<Duration>,
+    pub days: HashMap<Day, Duration>,
+}
+
+impl Timeouts {
+    /// Reads limits from the environment.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_vars(|key| env::var(key).ok())
+    }
+
+    fn from_vars(get: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let parse = |key: &str| -> Result<Option<Duration>, String> {
+            get(key)
+                .map(|value| match value.trim().parse() {
+                    Ok(secs) if secs > 0 => Ok(Duration::from_secs(secs)),
+                    _ => Err(format!("{key} must be a positive integer, got `{value}`.")),
+                })
+                .transpose()
+        };
+
+        let mut days = HashMap::new();
+
+        for day in all_days() {
+            if let Some(timeout) = parse(&day_env(day))? {
+                days.insert(day, timeout);
+            }
+        }
+
+        Ok(Self {
+            default: parse(TIMEOUT_ENV)?,
+            days,
+        })
+    }
+
+    /// Limit that applies to `day`, if any.
+    pub fn for_day(&self, day: Day) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.default)
+    }
+}
+
+fn day_env(day: Day) -> String {
+    format!("{TIMEOUT_ENV}_{day}")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Timeouts, TIMEOUT_ENV};
+    use crate::day;
+    use crate::template::test_vars;
+    use std::time::Duration;
+
+    #[test]
+    fn has_no_limit_by_default() {
+        let timeouts = Timeouts::from_vars(test_vars(&[])).unwrap();
+        assert_eq!(timeouts, Timeouts::default());
+        assert_eq!(timeouts.for_day(day!(6)), None);
+    }
+
+    #[test]
+    fn overrides_global_limit_per_day() {
+        let timeouts = Timeouts::from_vars(test_vars(&[
+            (TIMEOUT_ENV, "30"),
+            ("AOC_TIMEOUT_SECS_06", "120"),
+        ]))
+        .unwrap();
+        assert_eq!(timeouts.for_day(day!(1)), Some(Duration::from_secs(30)));
+        assert_eq!(timeouts.for_day(day!(6)), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn limits_single_days() {
+        let timeouts = Timeouts::from_vars(test_vars(&[("AOC_TIMEOUT_SECS_06", "5")])).unwrap();
+        assert_eq!(timeouts.for_day(day!(5)), None);
+        assert_eq!(timeouts.for_day(day!(6)), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_zero_limits() {
+        Timeouts::from_vars(test_vars(&[(TIMEOUT_ENV, "0")])).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_limits() {
+        Timeouts::from_vars(test_vars(&[("AOC_TIMEOUT_SECS_06", "soon")])).unwrap();
+    }
+}
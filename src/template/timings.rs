@@ -1,7 +1,7 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{bench_settings::BenchSettings, heap_stats::HeapStats, stats::Summary, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -9,11 +9,69 @@ static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Timing of the parse phase, for solutions that declare one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Summary>,
+    pub part_1_stats: Option<Summary>,
+    pub part_2_stats: Option<Summary>,
+    pub bench_settings: Option<BenchSettings>,
+    /// Heap usage over all phases, if measured with `time --memory`.
+    pub memory: Option<HeapStats>,
     pub total_nanos: f64,
 }
 
+/// Change in execution time of a single phase (parse, part 1 or part 2) between two timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub phase: &'static str,
+    pub old_nanos: f64,
+    pub new_nanos: f64,
+}
+
+impl Delta {
+    /// Relative change, e.g. `0.5` if the new timing is 50% slower.
+    pub fn relative(&self) -> f64 {
+        (self.new_nanos - self.old_nanos) / self.old_nanos
+    }
+
+    /// Whether the new timing is slower than the old one by more than `threshold` (relative).
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.relative() > threshold
+    }
+}
+
+impl Timing {
+    /// Execution time per phase in nanoseconds.
+    fn phase_nanos(&self) -> [(&'static str, Option<f64>); 3] {
+        let nanos = |timing: &Option<String>, stats: &Option<Summary>| {
+            stats
+                .as_ref()
+                .map(|s| s.mean.as_nanos() as f64)
+                .or_else(|| timing.as_deref().and_then(parse_nanos))
+        };
+
+        [
+            ("Parse", nanos(&self.parse, &self.parse_stats)),
+            ("Part 1", nanos(&self.part_1, &self.part_1_stats)),
+            ("Part 2", nanos(&self.part_2, &self.part_2_stats)),
+        ]
+    }
+}
+
+/// Parse a formatted duration (e.g. `74.13ns`) to nanoseconds.
+fn parse_nanos(s: &str) -> Option<f64> {
+    let parse = |postfix: &str| s.strip_suffix(postfix)?.trim().parse::<f64>().ok();
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    parse("ns")
+        .or_else(|| parse("µs").map(|x| x * 1000_f64))
+        .or_else(|| parse("ms").map(|x| x * 1_000_000_f64))
+        .or_else(|| parse("s").map(|x| x * 1_000_000_000_f64))
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -42,7 +100,16 @@ impl Timings {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            // keep heap metrics of a previous `--memory` run if this run did not measure them.
+            if timing.memory.is_none() {
+                timing.memory = self
+                    .data
+                    .iter()
+                    .find(|t| t.day == timing.day)
+                    .and_then(|t| t.memory.clone());
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,7 +118,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,6 +127,53 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Compare `self` against `baseline` for every phase that is present in both.
+    pub fn compare(&self, baseline: &Self) -> Vec<Delta> {
+        self.data
+            .iter()
+            .filter_map(|new| {
+                let old = baseline.data.iter().find(|t| t.day == new.day)?;
+                Some((new, old))
+            })
+            .flat_map(|(new, old)| {
+                new.phase_nanos()
+                    .into_iter()
+                    .zip(old.phase_nanos())
+                    .filter_map(|((phase, new_nanos), (_, old_nanos))| {
+                        Some(Delta {
+                            day: new.day,
+                            phase,
+                            old_nanos: old_nanos.filter(|x| *x > 0.0)?,
+                            new_nanos: new_nanos?,
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    /// Phases that have a timing in `baseline` but not in `self`, e.g. because the day timed out, a part
+    /// panicked or is no longer solved.
+    pub fn missing(&self, baseline: &Self) -> Vec<(Day, &'static str)> {
+        baseline
+            .data
+            .iter()
+            .flat_map(|old| {
+                let new = self.data.iter().find(|t| t.day == old.day);
+                old.phase_nanos()
+                    .into_iter()
+                    .filter(|(_, old_nanos)| old_nanos.is_some_and(|x| x > 0.0))
+                    .filter(move |(phase, _)| {
+                        !new.is_some_and(|new| {
+                            new.phase_nanos()
+                                .iter()
+                                .any(|(p, nanos)| p == phase && nanos.is_some())
+                        })
+                    })
+                    .map(|(phase, _)| (old.day, phase))
+            })
+            .collect()
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -87,7 +201,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
 
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
+
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -133,6 +254,54 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "bench_settings".into(),
+            value
+                .bench_settings
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,10 +335,52 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: the following keys are optional, timings stored before they were added lack them.
+        let parse = json
+            .get("parse")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() })
+            .cloned();
+
+        let parse_stats = match json.get("parse_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Summary::try_from(v)?),
+        };
+
+        let part_1_stats = match json.get("part_1_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Summary::try_from(v)?),
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Summary::try_from(v)?),
+        };
+
+        let bench_settings = match json.get("bench_settings") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchSettings::try_from(v)?),
+        };
+
+        let memory = match json.get("memory") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(HeapStats::try_from(v)?),
+        };
+
         Ok(Timing {
             day,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            bench_settings,
+            memory,
             total_nanos,
         })
     }
@@ -177,6 +388,153 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&BenchSettings> for JsonValue {
+    fn from(value: &BenchSettings) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "budget_ms".into(),
+            JsonValue::Number(value.budget.as_millis() as f64),
+        );
+        map.insert(
+            "min_samples".into(),
+            JsonValue::Number(value.min_samples as f64),
+        );
+        map.insert(
+            "max_samples".into(),
+            JsonValue::Number(value.max_samples as f64),
+        );
+        map.insert("warmup".into(), JsonValue::Number(value.warmup as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchSettings {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing bench settings to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!(
+                    "Expected timing bench_settings.{key} to be a number."
+                ))
+        };
+
+        Ok(BenchSettings {
+            budget: Duration::from_millis(number("budget_ms")? as u64),
+            min_samples: number("min_samples")? as u128,
+            max_samples: number("max_samples")? as u128,
+            warmup: number("warmup")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HeapStats> for JsonValue {
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HeapStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing memory.{key} to be a number."))
+        };
+
+        Ok(HeapStats {
+            total_bytes: number("total_bytes")? as u64,
+            peak_bytes: number("peak_bytes")? as u64,
+            allocations: number("allocations")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Summary> for JsonValue {
+    fn from(value: &Summary) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("std_dev_nanos".into(), nanos(value.std_dev));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        map.insert("max_nanos".into(), nanos(value.max));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Summary {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        Ok(Summary {
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            mean: duration("mean_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            min: duration("min_nanos")?,
+            median: duration("median_nanos")?,
+            p95: duration("p95_nanos")?,
+            max: duration("max_nanos")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -188,20 +546,38 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -210,6 +586,7 @@ mod tests {
 
     mod deserialization {
         use crate::{day, template::timings::Timings};
+        use std::time::Duration;
 
         #[test]
         fn handles_json_timings() {
@@ -223,6 +600,30 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "part_1_stats": { "samples": 10, "outliers": 1, "mean_nanos": 1000000, "std_dev_nanos": 100, "min_nanos": 900000, "median_nanos": 1000000, "p95_nanos": 1100000, "max_nanos": 1200000 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(stats.p95, Duration::from_nanos(1_100_000));
+            assert_eq!(timing.part_2_stats, None);
+            assert_eq!(timing.bench_settings, None);
+        }
+
+        #[test]
+        fn handles_json_bench_settings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000, "bench_settings": { "budget_ms": 250, "min_samples": 5, "max_samples": 50, "warmup": 3 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let settings = timings.data[0].bench_settings.as_ref().unwrap();
+            assert_eq!(settings.budget, Duration::from_millis(250));
+            assert_eq!(settings.min_samples, 5);
+            assert_eq!(settings.max_samples, 50);
+            assert_eq!(settings.warmup, 3);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -279,13 +680,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +700,19 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +720,113 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::timings::{Timing, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn computes_deltas_for_common_parts() {
+            let baseline = get_mock_timings();
+            let new = Timings {
+                data: vec![
+                    Timing {
+                        part_1: Some("15ms".into()),
+                        part_2: Some("20ms".into()),
+                        ..baseline.data[0].clone()
+                    },
+                    Timing {
+                        part_2: Some("80ms".into()),
+                        ..baseline.data[2].clone()
+                    },
+                ],
+            };
+
+            let deltas = new.compare(&baseline);
+            assert_eq!(deltas.len(), 3);
+
+            assert_eq!(deltas[0].day, day!(1));
+            assert_eq!(deltas[0].phase, "Part 1");
+            assert_eq!(deltas[0].relative(), 0.5);
+            assert_eq!(deltas[0].is_regression(0.1), true);
+            assert_eq!(deltas[0].is_regression(0.5), false);
+
+            assert_eq!(deltas[1].phase, "Part 2");
+            assert_eq!(deltas[1].relative(), 0.0);
+
+            // day 4 has no stored part 2, so only part 1 can be compared.
+            assert_eq!(deltas[2].day, day!(4));
+            assert_eq!(deltas[2].phase, "Part 1");
+        }
+
+        #[test]
+        fn skips_days_without_baseline() {
+            let new = get_mock_timings();
+            let deltas = new.compare(&Timings::default());
+            assert_eq!(deltas.len(), 0);
+        }
+
+        #[test]
+        fn reports_missing_phases() {
+            let baseline = get_mock_timings();
+            let new = Timings {
+                data: vec![Timing {
+                    part_2: None,
+                    ..baseline.data[0].clone()
+                }],
+            };
+
+            assert_eq!(
+                new.missing(&baseline),
+                vec![
+                    (day!(1), "Part 2"),
+                    (day!(2), "Part 1"),
+                    (day!(2), "Part 2"),
+                    (day!(4), "Part 1"),
+                ]
+            );
+            assert_eq!(baseline.missing(&baseline), vec![]);
+        }
+
+        #[test]
+        fn parses_formatted_durations() {
+            let baseline = Timings {
+                data: vec![Timing {
+                    part_1: Some("1.5µs".into()),
+                    part_2: Some("2s".into()),
+                    ..get_mock_timings().data[0].clone()
+                }],
+            };
+            let new = Timings {
+                data: vec![Timing {
+                    part_1: Some("750.0ns".into()),
+                    part_2: Some("3000.0ms".into()),
+                    ..get_mock_timings().data[0].clone()
+                }],
+            };
+
+            let deltas = new.compare(&baseline);
+            assert_eq!(deltas[0].relative(), -0.5);
+            assert_eq!(deltas[1].relative(), 0.5);
         }
     }
 
@@ -331,8 +844,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -351,8 +870,14 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    bench_settings: None,
+                    memory: None,
                     total_nanos: 0_f64,
                 }],
             };